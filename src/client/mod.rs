@@ -0,0 +1,26 @@
+use serde::Deserialize;
+use serde_json::Value;
+
+pub mod stdio;
+
+/// MCP protocol revision sent in the `initialize` request
+pub const PROTOCOL_VERSION: &str = "2025-06-18";
+
+/// Result of the JSON-RPC `initialize` handshake
+#[derive(Debug, Clone, Deserialize)]
+pub struct InitializeResult {
+    #[serde(rename = "protocolVersion")]
+    pub protocol_version: Option<String>,
+    #[serde(rename = "serverInfo")]
+    pub server_info: Option<ServerInfo>,
+    #[serde(default)]
+    pub capabilities: Value,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct ServerInfo {
+    pub name: Option<String>,
+    pub version: Option<String>,
+}
+
+pub use stdio::StdioClient;
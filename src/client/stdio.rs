@@ -0,0 +1,211 @@
+use crate::client::{InitializeResult, PROTOCOL_VERSION};
+use crate::error::McpClientError;
+use crate::model::{McpConfig, McpTransport};
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Most pages `list_all` follows before giving up on a server
+const MAX_PAGES: usize = 100;
+
+/// A JSON-RPC session with an MCP server spawned as a child process.
+/// Messages are newline-delimited JSON over the child's stdin/stdout.
+/// The child is killed when the client is dropped.
+pub struct StdioClient {
+    child: Child,
+    stdin: ChildStdin,
+    lines: Receiver<String>,
+    next_id: u64,
+    timeout: Duration,
+}
+
+impl StdioClient {
+    /// Spawn the server described by a stdio `McpConfig`
+    pub fn spawn(config: &McpConfig, timeout: Duration) -> Result<Self, McpClientError> {
+        if config.transport != McpTransport::Stdio {
+            return Err(McpClientError::Unsupported(format!(
+                "{} transport",
+                config.transport
+            )));
+        }
+
+        let command = config
+            .command
+            .as_deref()
+            .ok_or_else(|| McpClientError::Protocol("no command configured".to_string()))?;
+
+        let mut cmd = Command::new(command);
+        cmd.args(config.args.iter().flatten())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null());
+        if let Some(ref env) = config.env {
+            cmd.envs(env);
+        }
+
+        let mut child = cmd.spawn().map_err(McpClientError::Spawn)?;
+        let stdin = child.stdin.take().ok_or(McpClientError::Closed)?;
+        let stdout = child.stdout.take().ok_or(McpClientError::Closed)?;
+
+        // Read stdout on a separate thread so requests can time out
+        let (tx, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                match line {
+                    Ok(line) => {
+                        if tx.send(line).is_err() {
+                            break;
+                        }
+                    }
+                    Err(_) => break,
+                }
+            }
+        });
+
+        Ok(Self {
+            child,
+            stdin,
+            lines,
+            next_id: 1,
+            timeout,
+        })
+    }
+
+    /// Perform the `initialize` handshake and send `notifications/initialized`
+    pub fn initialize(&mut self) -> Result<InitializeResult, McpClientError> {
+        let result = self.request(
+            "initialize",
+            json!({
+                "protocolVersion": PROTOCOL_VERSION,
+                "capabilities": {},
+                "clientInfo": {
+                    "name": env!("CARGO_PKG_NAME"),
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+            }),
+        )?;
+
+        let init: InitializeResult = serde_json::from_value(result)
+            .map_err(|e| McpClientError::Protocol(format!("invalid initialize result: {}", e)))?;
+
+        self.notify("notifications/initialized", json!({}))?;
+        Ok(init)
+    }
+
+    /// Send a request and wait for the response with the matching id
+    pub fn request(&mut self, method: &str, params: Value) -> Result<Value, McpClientError> {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        self.send(&json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": method,
+            "params": params,
+        }))?;
+
+        let deadline = Instant::now() + self.timeout;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let line = match self.lines.recv_timeout(remaining) {
+                Ok(line) => line,
+                Err(RecvTimeoutError::Timeout) => {
+                    return Err(McpClientError::Timeout(self.timeout))
+                }
+                Err(RecvTimeoutError::Disconnected) => return Err(McpClientError::Closed),
+            };
+
+            // Skip log output, notifications and server-initiated requests
+            let message: Value = match serde_json::from_str(&line) {
+                Ok(message) => message,
+                Err(_) => continue,
+            };
+            if message.get("id").and_then(Value::as_u64) != Some(id)
+                || message.get("method").is_some()
+            {
+                continue;
+            }
+
+            if let Some(error) = message.get("error") {
+                return Err(McpClientError::Server {
+                    code: error.get("code").and_then(Value::as_i64).unwrap_or(0),
+                    message: error
+                        .get("message")
+                        .and_then(Value::as_str)
+                        .unwrap_or("unknown error")
+                        .to_string(),
+                });
+            }
+
+            return Ok(message.get("result").cloned().unwrap_or(Value::Null));
+        }
+    }
+
+    /// Call a paginated `*/list` method, following `nextCursor` until exhausted,
+    /// and collect the array stored under `key` in each page. A cursor seen
+    /// before ends the listing, so servers that loop cannot hang it.
+    pub fn list_all(&mut self, method: &str, key: &str) -> Result<Vec<Value>, McpClientError> {
+        let mut items = Vec::new();
+        let mut cursor: Option<String> = None;
+        let mut seen = HashSet::new();
+
+        for _ in 0..MAX_PAGES {
+            let params = match cursor {
+                Some(ref c) => json!({ "cursor": c }),
+                None => json!({}),
+            };
+            let page = self.request(method, params)?;
+
+            match page.get(key) {
+                Some(Value::Array(page_items)) => items.extend(page_items.iter().cloned()),
+                Some(_) => {
+                    return Err(McpClientError::Protocol(format!(
+                        "{}: '{}' is not an array",
+                        method, key
+                    )))
+                }
+                None => {}
+            }
+
+            match page.get("nextCursor").and_then(Value::as_str) {
+                Some(next) if !next.is_empty() && seen.insert(next.to_string()) => {
+                    cursor = Some(next.to_string());
+                }
+                _ => return Ok(items),
+            }
+        }
+
+        Err(McpClientError::Protocol(format!(
+            "{}: more than {} pages",
+            method, MAX_PAGES
+        )))
+    }
+
+    fn notify(&mut self, method: &str, params: Value) -> Result<(), McpClientError> {
+        self.send(&json!({
+            "jsonrpc": "2.0",
+            "method": method,
+            "params": params,
+        }))
+    }
+
+    fn send(&mut self, message: &Value) -> Result<(), McpClientError> {
+        let mut line = message.to_string();
+        line.push('\n');
+        self.stdin
+            .write_all(line.as_bytes())
+            .and_then(|_| self.stdin.flush())
+            .map_err(|_| McpClientError::Closed)
+    }
+}
+
+impl Drop for StdioClient {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
@@ -1,12 +1,22 @@
 use std::fmt;
 use std::path::PathBuf;
+use std::time::Duration;
 
 #[derive(Debug)]
 pub enum CliError {
     LocalConfigError(LocalSourceError),
+    McpClientError(McpClientError),
+    ApiError(ApiSourceError),
+    MissingApiKey,
     Io(std::io::Error),
     Serialize(serde_json::Error),
     NotFound(String, Vec<String>),
+    UnknownSource(String, Vec<String>),
+    InvalidName(String, Vec<String>),
+    AlreadyExists(String),
+    InvalidSkill(PathBuf, Vec<String>),
+    Ambiguous(String, Vec<String>),
+    InvalidArgument(String),
 }
 
 #[derive(Debug)]
@@ -14,12 +24,46 @@ pub enum LocalSourceError {
     ConfigNotFound(PathBuf),
     InvalidConfig(String),
     PermissionDenied(PathBuf),
+    InvalidFrontmatter(PathBuf, FrontmatterError),
+}
+
+/// A SKILL.md frontmatter block that is not valid YAML
+#[derive(Debug)]
+pub struct FrontmatterError {
+    /// 1-based line in the file, when known
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+#[derive(Debug)]
+pub enum ApiSourceError {
+    Request(String),
+    Status { code: u16, message: String },
+    InvalidResponse(String),
+}
+
+#[derive(Debug)]
+pub enum McpClientError {
+    Spawn(std::io::Error),
+    Timeout(Duration),
+    Closed,
+    Protocol(String),
+    Server { code: i64, message: String },
+    Unsupported(String),
 }
 
 impl fmt::Display for CliError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             CliError::LocalConfigError(e) => write!(f, "Failed to read local configuration: {}", e),
+            CliError::McpClientError(e) => write!(f, "MCP server error: {}", e),
+            CliError::ApiError(e) => write!(f, "API request failed: {}", e),
+            CliError::MissingApiKey => {
+                write!(
+                    f,
+                    "Missing API key. Set CLAUDE_API_KEY environment variable"
+                )
+            }
             CliError::Io(e) => write!(f, "IO error: {}", e),
             CliError::Serialize(e) => write!(f, "Serialization error: {}", e),
             CliError::NotFound(name, suggestions) => {
@@ -32,6 +76,39 @@ impl fmt::Display for CliError {
                 }
                 Ok(())
             }
+            CliError::InvalidName(name, problems) => {
+                write!(f, "Invalid name: '{}'", name)?;
+                for problem in problems {
+                    write!(f, "\n  - {}", problem)?;
+                }
+                Ok(())
+            }
+            CliError::AlreadyExists(what) => write!(f, "Already exists: {}", what),
+            CliError::InvalidArgument(message) => write!(f, "Invalid argument: {}", message),
+            CliError::Ambiguous(name, places) => {
+                write!(
+                    f,
+                    "'{}' is defined in several places; pick one with --scope:",
+                    name
+                )?;
+                for place in places {
+                    write!(f, "\n  - {}", place)?;
+                }
+                Ok(())
+            }
+            CliError::InvalidSkill(path, problems) => {
+                write!(f, "Not a valid skill: {}", path.display())?;
+                for problem in problems {
+                    write!(f, "\n  - {}", problem)?;
+                }
+                Ok(())
+            }
+            CliError::UnknownSource(name, available) => write!(
+                f,
+                "Unknown source: '{}' (available: {})",
+                name,
+                available.join(", ")
+            ),
         }
     }
 }
@@ -48,24 +125,91 @@ impl fmt::Display for LocalSourceError {
             LocalSourceError::PermissionDenied(p) => {
                 write!(f, "Permission denied: {}", p.display())
             }
+            LocalSourceError::InvalidFrontmatter(p, e) => {
+                let file = p.file_name().unwrap_or(p.as_os_str());
+                write!(
+                    f,
+                    "Invalid frontmatter in {}: {}",
+                    file.to_string_lossy(),
+                    e
+                )
+            }
         }
     }
 }
 
 impl std::error::Error for LocalSourceError {}
 
+impl fmt::Display for FrontmatterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for FrontmatterError {}
+
+impl fmt::Display for ApiSourceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiSourceError::Request(s) => write!(f, "{}", s),
+            ApiSourceError::Status { code, message } => write!(f, "HTTP {}: {}", code, message),
+            ApiSourceError::InvalidResponse(s) => write!(f, "invalid response: {}", s),
+        }
+    }
+}
+
+impl std::error::Error for ApiSourceError {}
+
+impl fmt::Display for McpClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            McpClientError::Spawn(e) => write!(f, "failed to start server: {}", e),
+            McpClientError::Timeout(d) => write!(f, "timed out after {}s", d.as_secs_f32()),
+            McpClientError::Closed => write!(f, "server closed the connection"),
+            McpClientError::Protocol(s) => write!(f, "protocol error: {}", s),
+            McpClientError::Server { code, message } => {
+                write!(f, "server returned error {}: {}", code, message)
+            }
+            McpClientError::Unsupported(s) => write!(f, "unsupported: {}", s),
+        }
+    }
+}
+
+impl std::error::Error for McpClientError {}
+
 impl From<LocalSourceError> for CliError {
     fn from(e: LocalSourceError) -> Self {
         CliError::LocalConfigError(e)
     }
 }
 
+impl From<McpClientError> for CliError {
+    fn from(e: McpClientError) -> Self {
+        CliError::McpClientError(e)
+    }
+}
+
+impl From<ApiSourceError> for CliError {
+    fn from(e: ApiSourceError) -> Self {
+        CliError::ApiError(e)
+    }
+}
+
 impl From<std::io::Error> for CliError {
     fn from(e: std::io::Error) -> Self {
         CliError::Io(e)
     }
 }
 
+impl From<zip::result::ZipError> for CliError {
+    fn from(e: zip::result::ZipError) -> Self {
+        CliError::Io(e.into())
+    }
+}
+
 impl From<serde_json::Error> for CliError {
     fn from(e: serde_json::Error) -> Self {
         CliError::Serialize(e)
@@ -1,10 +1,18 @@
+pub mod client;
 pub mod error;
 pub mod model;
 pub mod output;
+pub mod secrets;
 pub mod service;
 pub mod source;
 
+#[cfg(test)]
+mod test_support;
+
 pub use error::CliError;
-pub use model::{ClaudeList, DetailItem, McpServer, Skill};
-pub use output::{DetailFormatter, Formatter, JsonFormatter, TableFormatter};
+pub use model::{Agent, ClaudeList, Command, DetailItem, Hook, McpServer, Plugin, Skill};
+pub use output::{
+    AuditFormatter, ConflictFormatter, DetailFormatter, Formatter, JsonFormatter, LintFormatter,
+    SarifFormatter, TableFormatter,
+};
 pub use service::ListService;
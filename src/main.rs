@@ -1,6 +1,12 @@
 use clap::{Parser, Subcommand};
-use claude_list::output::{DetailFormatter, Formatter, JsonFormatter, TableFormatter};
-use claude_list::service::ListService;
+use claude_list::error::CliError;
+use claude_list::model::{ConnectionStatus, DetailItem, McpChange, Risk, Scope};
+use claude_list::output::{
+    AuditFormatter, ConflictFormatter, DetailFormatter, Formatter, JsonFormatter, LintFormatter,
+    SarifFormatter, TableFormatter,
+};
+use claude_list::service::{ListService, NewSkill, API_SOURCE, LOCAL_SOURCES};
+use claude_list::source::McpServerSpec;
 
 #[derive(Parser)]
 #[clap(name = "claude-list")]
@@ -17,53 +23,469 @@ struct Cli {
     /// Show verbose output
     #[clap(short, long, global = true)]
     verbose: bool,
+
+    /// Only read local configuration
+    #[clap(short, long, global = true, conflicts_with_all = &["api", "source"])]
+    local: bool,
+
+    /// Only read skills from the API (requires CLAUDE_API_KEY)
+    #[clap(short, long, global = true, conflicts_with = "source")]
+    api: bool,
+
+    /// Only read from the named sources: user, project, plugin or api
+    #[clap(
+        long,
+        global = true,
+        value_name = "NAME",
+        multiple_occurrences = true,
+        use_value_delimiter = true
+    )]
+    source: Vec<String>,
+
+    /// Config directory to read instead of $CLAUDE_CONFIG_DIR or ~/.claude.
+    /// Repeat to list several directories side by side.
+    #[clap(long, global = true, value_name = "DIR", multiple_occurrences = true)]
+    config_dir: Vec<std::path::PathBuf>,
+
+    /// Include per-project MCP servers from all projects in ~/.claude.json
+    #[clap(long, global = true)]
+    all_projects: bool,
+
+    /// Start each MCP server and check that it responds
+    #[clap(long, global = true)]
+    check: bool,
+
+    /// Print MCP env values, headers and arguments that look like secrets
+    /// instead of masking them
+    #[clap(long, global = true)]
+    show_secrets: bool,
+
+    /// Look up each stdio MCP server's command on PATH and check its runtime
+    #[clap(long, global = true)]
+    doctor: bool,
+
+    /// Seconds to wait for an MCP server during a health check
+    #[clap(long, global = true, default_value = "10")]
+    timeout: u64,
 }
 
 #[derive(Subcommand)]
 enum Commands {
-    /// List all skills and MCP servers (default)
+    /// List all skills, MCP servers, commands, agents, hooks and plugins (default)
     List,
     /// List only skills
     Skills,
     /// List only MCP servers
     Mcps,
-    /// Show details of a skill or MCP server
+    /// List only custom slash commands
+    #[clap(name = "commands")]
+    Slash,
+    /// Check that each MCP server starts and completes the handshake
+    Check,
+    /// List only subagents
+    Agents,
+    /// List hooks configured in user, project, local and managed settings
+    Hooks,
+    /// List installed plugins and what they contribute
+    Plugins,
+    /// List MCP server names defined in more than one file and which one wins
+    Conflicts,
+    /// Look for risky MCP servers, skill scripts and hooks; exits non-zero on
+    /// findings at or above --fail-on
+    Audit {
+        /// Output SARIF 2.1.0 for code scanning tools
+        #[clap(long, conflicts_with = "json")]
+        sarif: bool,
+        /// Lowest risk that makes the command fail: low, medium or high
+        #[clap(long, value_name = "RISK", default_value = "high")]
+        fail_on: Risk,
+    },
+    /// Check skills against the SKILL.md spec; exits non-zero on errors
+    Lint {
+        /// Only check the skill with this name
+        skill: Option<String>,
+    },
+    /// Create a new item from a template
+    New {
+        #[clap(subcommand)]
+        item: NewItem,
+    },
+    /// Install a skill from a directory, zip archive or git URL
+    Install {
+        /// Skill directory, .zip archive or git repository URL
+        #[clap(value_name = "PATH_OR_URL")]
+        from: String,
+        /// Install into the project instead of the user config directory
+        #[clap(long)]
+        project: bool,
+        /// Overwrite an installed skill and ignore skills of the same name elsewhere
+        #[clap(long)]
+        force: bool,
+        /// Show what would be installed without writing anything
+        #[clap(long)]
+        dry_run: bool,
+    },
+    /// Remove a user or project skill
+    Uninstall {
+        /// Name of the skill to remove
+        name: String,
+        /// Scope to remove from, when the name is installed in both
+        #[clap(long, short)]
+        scope: Option<Scope>,
+        /// Show what would be removed without deleting anything
+        #[clap(long)]
+        dry_run: bool,
+    },
+    /// Add, remove, rename, enable or disable MCP servers
+    Mcp {
+        #[clap(subcommand)]
+        action: McpAction,
+    },
+    /// Show details of a skill, MCP server or agent
     Show {
-        /// Name of the skill or MCP server to show
+        /// Name of the skill, MCP server or agent to show
         name: String,
-        /// Show raw file content (for skills)
+        /// Show raw file content (for skills and agents)
         #[clap(long)]
         raw: bool,
     },
 }
 
+#[derive(Subcommand)]
+enum NewItem {
+    /// Create ~/.claude/skills/<NAME>/SKILL.md with valid frontmatter
+    Skill {
+        /// Lowercase letters, digits and hyphens
+        name: String,
+        /// Create the skill in the project instead of the user config directory
+        #[clap(long)]
+        project: bool,
+        /// Description for the frontmatter
+        #[clap(long, short)]
+        description: Option<String>,
+        /// Also create a scripts/ directory
+        #[clap(long)]
+        scripts: bool,
+        /// Also create a references/ directory
+        #[clap(long)]
+        references: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum McpAction {
+    /// Add a server: `mcp add NAME -- COMMAND [ARGS]...` or `mcp add NAME --url URL`
+    Add {
+        name: String,
+        /// user (~/.claude.json), local (this project in ~/.claude.json) or project (.mcp.json)
+        #[clap(long, short, default_value = "local")]
+        scope: Scope,
+        /// stdio, http or sse; defaults to stdio with a command and http with --url
+        #[clap(long, short)]
+        transport: Option<String>,
+        /// URL of a remote server
+        #[clap(long)]
+        url: Option<String>,
+        /// Environment variable for a stdio server
+        #[clap(long, short, value_name = "KEY=VALUE", multiple_occurrences = true)]
+        env: Vec<String>,
+        /// Header for a remote server
+        #[clap(
+            long,
+            short = 'H',
+            value_name = "NAME: VALUE",
+            multiple_occurrences = true
+        )]
+        header: Vec<String>,
+        /// Command and arguments of a stdio server
+        #[clap(last = true)]
+        command: Vec<String>,
+    },
+    /// Remove a server
+    Remove {
+        name: String,
+        /// Scope to remove from, when the name is defined in several
+        #[clap(long, short)]
+        scope: Option<Scope>,
+    },
+    /// Enable a server for the current project
+    Enable {
+        name: String,
+        #[clap(long, short)]
+        scope: Option<Scope>,
+    },
+    /// Disable a server for the current project
+    Disable {
+        name: String,
+        #[clap(long, short)]
+        scope: Option<Scope>,
+    },
+    /// Rename a server, keeping its place in the file
+    Rename {
+        name: String,
+        new_name: String,
+        #[clap(long, short)]
+        scope: Option<Scope>,
+    },
+}
+
 fn main() {
     let cli = Cli::parse();
-    let service = ListService::new();
+    let sources = if cli.local {
+        LOCAL_SOURCES
+            .iter()
+            .map(|(name, _, _)| name.to_string())
+            .collect()
+    } else if cli.api {
+        vec![API_SOURCE.to_string()]
+    } else {
+        cli.source.clone()
+    };
+    let service = ListService::new()
+        .with_sources(sources)
+        .with_config_dirs(cli.config_dir.clone())
+        .with_all_projects(cli.all_projects)
+        .with_timeout(std::time::Duration::from_secs(cli.timeout));
+    let check_command = matches!(cli.command, Some(Commands::Check));
 
     // Handle Show command separately
     if let Some(Commands::Show { name, raw }) = cli.command {
-        let detail = service.show(&name).unwrap_or_else(|e| {
+        let mut detail = service.show(&name).unwrap_or_else(|e| {
             eprintln!("Error: {}", e);
             std::process::exit(1);
         });
+        for warning in service.take_warnings() {
+            eprintln!("Warning: {}", warning);
+        }
+
+        if let DetailItem::Mcp(ref mut mcp) = detail {
+            if cli.doctor {
+                service.resolve_mcp(mcp);
+            }
+            // Listing what the server exposes means starting it
+            if cli.check {
+                service.inspect_mcp(mcp);
+            }
+        }
 
         let output: Box<dyn DetailFormatter> = if cli.json {
+            Box::new(JsonFormatter::new().with_show_secrets(cli.show_secrets))
+        } else {
+            Box::new(TableFormatter::new().with_show_secrets(cli.show_secrets))
+        };
+
+        let formatted = output.format_detail(&detail, raw).unwrap_or_else(|e| {
+            eprintln!("Error formatting output: {}", e);
+            std::process::exit(1);
+        });
+        println!("{}", formatted);
+        return;
+    }
+
+    if let Some(Commands::New { ref item }) = cli.command {
+        let NewItem::Skill {
+            ref name,
+            project,
+            ref description,
+            scripts,
+            references,
+        } = *item;
+        let mut skill = NewSkill::new(name.clone())
+            .with_scripts(scripts)
+            .with_references(references);
+        if let Some(ref description) = description {
+            skill = skill.with_description(description.clone());
+        }
+        let scope = if project { Scope::Project } else { Scope::User };
+
+        let dir = service.new_skill(&skill, scope).unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        });
+        if cli.json {
+            let created = serde_json::json!({ "name": name, "scope": scope, "path": dir });
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&created).unwrap_or_default()
+            );
+        } else {
+            println!("Created {} skill '{}' at {}", scope, name, dir.display());
+        }
+        return;
+    }
+
+    if let Some(Commands::Mcp { ref action }) = cli.command {
+        let change = run_mcp_action(&service, action).unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        });
+        if cli.json {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&change).unwrap_or_default()
+            );
+            return;
+        }
+
+        let action = match change.renamed_from {
+            Some(ref from) => format!("Renamed {} MCP server '{}' to", change.scope, from),
+            None => {
+                let mut action = change.action.clone();
+                action[..1].make_ascii_uppercase();
+                format!("{} {} MCP server", action, change.scope)
+            }
+        };
+        print!("{} '{}' in {}", action, change.name, change.file.display());
+        match change.backup {
+            Some(ref backup) => println!(" (backup: {})", backup.display()),
+            None => println!(),
+        }
+        return;
+    }
+
+    let change = match cli.command {
+        Some(Commands::Install {
+            ref from,
+            project,
+            force,
+            dry_run,
+        }) => {
+            let scope = if project { Scope::Project } else { Scope::User };
+            Some(service.install(from, scope, force, dry_run))
+        }
+        Some(Commands::Uninstall {
+            ref name,
+            scope,
+            dry_run,
+        }) => Some(service.uninstall(name, scope, dry_run)),
+        _ => None,
+    };
+    if let Some(change) = change {
+        let change = change.unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        });
+        if cli.json {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&change).unwrap_or_default()
+            );
+            return;
+        }
+
+        let installing = matches!(cli.command, Some(Commands::Install { .. }));
+        let action = match (installing, change.dry_run) {
+            (true, false) if change.replaced => "Replaced",
+            (true, false) => "Installed",
+            (true, true) => "Would install",
+            (false, false) => "Removed",
+            (false, true) => "Would remove",
+        };
+        println!(
+            "{} {} skill '{}' at {}",
+            action,
+            change.scope,
+            change.name,
+            change.path.display()
+        );
+        for file in &change.files {
+            println!("  {}", file.display());
+        }
+        for skill in &change.collisions {
+            let location = skill
+                .path
+                .as_ref()
+                .map_or_else(|| skill.source.to_string(), |p| p.display().to_string());
+            eprintln!(
+                "Warning: a {} skill named '{}' also exists at {}",
+                skill.scope, skill.name, location
+            );
+        }
+        return;
+    }
+
+    if let Some(Commands::Conflicts) = cli.command {
+        let conflicts = service.mcp_conflicts().unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        });
+        for warning in service.take_warnings() {
+            eprintln!("Warning: {}", warning);
+        }
+
+        let output: Box<dyn ConflictFormatter> = if cli.json {
+            Box::new(JsonFormatter::new().with_show_secrets(cli.show_secrets))
+        } else {
+            Box::new(TableFormatter::new().with_show_secrets(cli.show_secrets))
+        };
+        let formatted = output.format_conflicts(&conflicts).unwrap_or_else(|e| {
+            eprintln!("Error formatting output: {}", e);
+            std::process::exit(1);
+        });
+        println!("{}", formatted);
+        return;
+    }
+
+    if let Some(Commands::Audit { sarif, fail_on }) = cli.command {
+        let report = service.audit().unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        });
+        if !cli.json {
+            for warning in &report.warnings {
+                eprintln!("Warning: {}", warning);
+            }
+        }
+
+        let output: Box<dyn AuditFormatter> = if sarif {
+            Box::new(SarifFormatter::new())
+        } else if cli.json {
             Box::new(JsonFormatter::new())
         } else {
             Box::new(TableFormatter::new())
         };
+        let formatted = output.format_audit(&report).unwrap_or_else(|e| {
+            eprintln!("Error formatting output: {}", e);
+            std::process::exit(1);
+        });
+        println!("{}", formatted);
 
-        let formatted = output.format_detail(&detail, raw).unwrap_or_else(|e| {
+        if report.reaches(fail_on) {
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    if let Some(Commands::Lint { ref skill }) = cli.command {
+        let report = service.lint(skill.as_deref()).unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        });
+        if !cli.json {
+            for warning in &report.warnings {
+                eprintln!("Warning: {}", warning);
+            }
+        }
+
+        let output: Box<dyn LintFormatter> = if cli.json {
+            Box::new(JsonFormatter::new())
+        } else {
+            Box::new(TableFormatter::new())
+        };
+        let formatted = output.format_lint(&report).unwrap_or_else(|e| {
             eprintln!("Error formatting output: {}", e);
             std::process::exit(1);
         });
         println!("{}", formatted);
+
+        if report.has_errors() {
+            std::process::exit(1);
+        }
         return;
     }
 
     // Determine what to list based on subcommand
-    let data = match cli.command {
+    let mut data = match cli.command {
         Some(Commands::Skills) => {
             let skills = service.list_skills().unwrap_or_else(|e| {
                 eprintln!("Error: {}", e);
@@ -71,17 +493,57 @@ fn main() {
             });
             claude_list::model::ClaudeList {
                 skills,
-                mcps: Vec::new(),
+                ..Default::default()
             }
         }
-        Some(Commands::Mcps) => {
+        Some(Commands::Mcps) | Some(Commands::Check) => {
             let mcps = service.list_mcps().unwrap_or_else(|e| {
                 eprintln!("Error: {}", e);
                 std::process::exit(1);
             });
             claude_list::model::ClaudeList {
-                skills: Vec::new(),
                 mcps,
+                ..Default::default()
+            }
+        }
+        Some(Commands::Slash) => {
+            let commands = service.list_commands().unwrap_or_else(|e| {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            });
+            claude_list::model::ClaudeList {
+                commands,
+                ..Default::default()
+            }
+        }
+        Some(Commands::Agents) => {
+            let agents = service.list_agents().unwrap_or_else(|e| {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            });
+            claude_list::model::ClaudeList {
+                agents,
+                ..Default::default()
+            }
+        }
+        Some(Commands::Hooks) => {
+            let hooks = service.list_hooks().unwrap_or_else(|e| {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            });
+            claude_list::model::ClaudeList {
+                hooks,
+                ..Default::default()
+            }
+        }
+        Some(Commands::Plugins) => {
+            let plugins = service.list_plugins().unwrap_or_else(|e| {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            });
+            claude_list::model::ClaudeList {
+                plugins,
+                ..Default::default()
             }
         }
         _ => {
@@ -93,11 +555,35 @@ fn main() {
         }
     };
 
+    data.warnings.extend(service.take_warnings());
+
+    if cli.check || check_command {
+        service.check_mcps(&mut data.mcps);
+    }
+    if cli.doctor {
+        service.resolve_mcps(&mut data.mcps);
+    }
+    // Only verbose tables and JSON show token counts
+    if cli.verbose || cli.json {
+        service.estimate_tokens(&mut data.skills);
+    }
+
+    // JSON output carries warnings in its `warnings` array
+    if !cli.json {
+        for warning in &data.warnings {
+            eprintln!("Warning: {}", warning);
+        }
+    }
+
     // Output formatting
     let output: Box<dyn Formatter> = if cli.json {
-        Box::new(JsonFormatter::new())
+        Box::new(JsonFormatter::new().with_show_secrets(cli.show_secrets))
     } else {
-        Box::new(TableFormatter::new().with_verbose(cli.verbose))
+        Box::new(
+            TableFormatter::new()
+                .with_verbose(cli.verbose)
+                .with_show_secrets(cli.show_secrets),
+        )
     };
 
     let formatted = output.format(&data).unwrap_or_else(|e| {
@@ -105,4 +591,75 @@ fn main() {
         std::process::exit(1);
     });
     println!("{}", formatted);
+
+    // `check` fails when any server could not be reached
+    if check_command
+        && data.mcps.iter().any(|m| {
+            matches!(
+                m.status,
+                ConnectionStatus::Error(_) | ConnectionStatus::Disconnected
+            )
+        })
+    {
+        std::process::exit(1);
+    }
+}
+
+fn run_mcp_action(service: &ListService, action: &McpAction) -> Result<McpChange, CliError> {
+    match *action {
+        McpAction::Add {
+            ref name,
+            scope,
+            ref transport,
+            ref url,
+            ref env,
+            ref header,
+            ref command,
+        } => {
+            let mut spec = match (url, command.split_first()) {
+                (Some(url), _) => McpServerSpec::remote(url.clone()),
+                (None, Some((command, args))) => {
+                    McpServerSpec::stdio(command.clone(), args.to_vec())
+                }
+                (None, None) => {
+                    return Err(CliError::InvalidArgument(
+                        "give a command after `--` or a --url".to_string(),
+                    ))
+                }
+            };
+            if let Some(ref transport) = transport {
+                spec = spec.with_transport(transport.clone());
+            }
+            for pair in env {
+                let (key, value) = split_pair(pair, '=')?;
+                spec = spec.with_env(key, value);
+            }
+            for pair in header {
+                let (key, value) = split_pair(pair, ':')?;
+                spec = spec.with_header(key, value);
+            }
+            service.mcp_add(name, scope, &spec)
+        }
+        McpAction::Remove { ref name, scope } => service.mcp_remove(name, scope),
+        McpAction::Enable { ref name, scope } => service.mcp_set_enabled(name, true, scope),
+        McpAction::Disable { ref name, scope } => service.mcp_set_enabled(name, false, scope),
+        McpAction::Rename {
+            ref name,
+            ref new_name,
+            scope,
+        } => service.mcp_rename(name, new_name, scope),
+    }
+}
+
+/// `KEY=VALUE` or `Name: value`
+fn split_pair(pair: &str, separator: char) -> Result<(String, String), CliError> {
+    match pair.split_once(separator) {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.trim().to_string()))
+        }
+        _ => Err(CliError::InvalidArgument(format!(
+            "expected KEY{}VALUE, got '{}'",
+            separator, pair
+        ))),
+    }
 }
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, Serialize)]
@@ -8,12 +9,87 @@ pub struct SkillDetail {
     pub start_matter: SkillStartMatter,
     pub content: String,
     pub path: PathBuf,
+    pub scope: Scope,
+    pub source: SourceType,
+    pub version: Option<String>,
+    /// Skills API id, for skills uploaded to the workspace
+    pub id: Option<String>,
+    /// Every file in the skill directory, SKILL.md included
+    pub files: Vec<SkillFile>,
+}
+
+/// A file bundled in a skill directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillFile {
+    /// Relative to the skill directory
+    pub path: PathBuf,
+    pub size: u64,
+    pub kind: SkillFileKind,
+    pub executable: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SkillFileKind {
+    /// SKILL.md itself
+    Skill,
+    Script,
+    Reference,
+    Asset,
+}
+
+impl std::fmt::Display for SkillFileKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SkillFileKind::Skill => write!(f, "skill"),
+            SkillFileKind::Script => write!(f, "script"),
+            SkillFileKind::Reference => write!(f, "reference"),
+            SkillFileKind::Asset => write!(f, "asset"),
+        }
+    }
 }
 
+/// SKILL.md frontmatter. Keys without a field of their own are kept in `extra`.
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 pub struct SkillStartMatter {
     pub name: Option<String>,
     pub description: Option<String>,
+    #[serde(default, deserialize_with = "de_scalar")]
+    pub version: Option<String>,
+    #[serde(
+        rename = "allowed-tools",
+        default,
+        deserialize_with = "de_tool_list",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub allowed_tools: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, Value>,
+}
+
+/// Accept a version written as a string or a bare number such as `1.0`;
+/// see `scalar_string` for how numbers are read
+fn de_scalar<'de, D: serde::Deserializer<'de>>(d: D) -> Result<Option<String>, D::Error> {
+    let value = Option::<Value>::deserialize(d)?;
+    Ok(value
+        .as_ref()
+        .and_then(crate::source::frontmatter::scalar_string))
+}
+
+/// `allowed-tools` may be a list or a comma-separated string
+fn de_tool_list<'de, D: serde::Deserializer<'de>>(d: D) -> Result<Vec<String>, D::Error> {
+    Ok(match Option::<Value>::deserialize(d)? {
+        Some(Value::String(s)) => crate::source::frontmatter::split_list(&s),
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(crate::source::frontmatter::scalar_string)
+            .collect(),
+        _ => Vec::new(),
+    })
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -22,12 +98,73 @@ pub struct McpDetail {
     pub config: crate::model::McpConfig,
     pub source_path: PathBuf,
     pub source_type: String,
+    pub scope: Scope,
+    pub project: Option<PathBuf>,
+    pub status: ConnectionStatus,
+    pub health: Option<McpHealth>,
+    #[serde(default)]
+    pub disabled: bool,
+    #[serde(default)]
+    pub unapproved: bool,
+    /// What the server exposes, listed after a successful handshake
+    pub capabilities: Option<McpCapabilities>,
+    /// Where the command was found on PATH
+    #[serde(default)]
+    pub resolution: Option<CommandResolution>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct McpCapabilities {
+    pub tools: Vec<McpTool>,
+    pub resources: Vec<McpResource>,
+    pub prompts: Vec<McpPrompt>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpTool {
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(rename(deserialize = "inputSchema"))]
+    pub input_schema: Option<Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpResource {
+    pub uri: String,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    #[serde(rename(deserialize = "mimeType"))]
+    pub mime_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpPrompt {
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub arguments: Vec<McpPromptArgument>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpPromptArgument {
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub required: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AgentDetail {
+    #[serde(flatten)]
+    pub agent: Agent,
+    pub content: String,
 }
 
 #[derive(Debug, Clone, Serialize)]
 pub enum DetailItem {
-    Skill(SkillDetail),
-    Mcp(McpDetail),
+    Skill(Box<SkillDetail>),
+    Mcp(Box<McpDetail>),
+    Agent(AgentDetail),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,8 +172,38 @@ pub struct Skill {
     pub name: String,
     pub version: Option<String>,
     pub source: SourceType,
+    pub scope: Scope,
     pub path: Option<PathBuf>,
     pub description: Option<String>,
+    /// Plugin that ships this skill, as `name@marketplace`
+    pub plugin: Option<String>,
+    /// Skills API id, for skills uploaded to the workspace
+    pub id: Option<String>,
+    /// The local copy of a merged skill
+    #[serde(default)]
+    pub local: Option<LocalInfo>,
+    /// The uploaded copy of a merged skill
+    #[serde(default)]
+    pub api: Option<ApiInfo>,
+    /// Set on merged skills whose local copy differs from the uploaded one
+    #[serde(default)]
+    pub drift: bool,
+    #[serde(default)]
+    pub config_dir: Option<PathBuf>,
+    /// Estimated context cost
+    #[serde(default)]
+    pub tokens: Option<SkillTokens>,
+}
+
+/// Approximate token counts of a skill
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct SkillTokens {
+    /// Name and description, always in context
+    pub metadata: usize,
+    /// SKILL.md body, loaded when the skill is used
+    pub body: Option<usize>,
+    /// Files the body links to
+    pub references: Option<usize>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -45,23 +212,216 @@ pub struct McpServer {
     pub status: ConnectionStatus,
     pub config: Option<McpConfig>,
     pub source: SourceType,
+    pub scope: Scope,
+    /// Project path for servers registered under `projects` in `~/.claude.json`
+    pub project: Option<PathBuf>,
+    /// Handshake details, filled in by a health check
+    pub health: Option<McpHealth>,
+    /// Plugin that ships this server, as `name@marketplace`
+    pub plugin: Option<String>,
+    #[serde(default)]
+    pub config_dir: Option<PathBuf>,
+    /// Turned off for the current project
+    #[serde(default)]
+    pub disabled: bool,
+    /// A `.mcp.json` server not approved in settings, which Claude Code
+    /// does not start
+    #[serde(default)]
+    pub unapproved: bool,
+    /// File the server is defined in
+    #[serde(default)]
+    pub origin: Option<PathBuf>,
+    /// Where the command was found on PATH, filled in by `--doctor`
+    #[serde(default)]
+    pub resolution: Option<CommandResolution>,
+}
+
+/// A stdio server's command looked up on PATH, as Claude Code would start it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandResolution {
+    /// The command as configured
+    pub command: String,
+    /// Absolute path of the binary; `None` when it was not found
+    pub path: Option<PathBuf>,
+    pub executable: bool,
+    /// Runtime the command needs, e.g. `node` for `npx`
+    pub runtime: Option<String>,
+    /// First line of `<runtime> --version`; `None` when it could not be run
+    pub runtime_version: Option<String>,
+    /// Whether `--version` was run. Only binaries found through our own PATH
+    /// are; paths and `PATH` entries from the server's config are not.
+    #[serde(default)]
+    pub checked: bool,
+}
+
+impl CommandResolution {
+    /// Why the server cannot start, if the binary or its runtime is missing
+    pub fn problem(&self) -> Option<String> {
+        match self.path {
+            None if self.command.contains('/') => Some(format!("{} not found", self.command)),
+            None => Some(format!("{} not found on PATH", self.command)),
+            Some(ref path) if !self.executable => {
+                Some(format!("{} is not executable", path.display()))
+            }
+            Some(_) => match (&self.runtime, &self.runtime_version) {
+                (Some(runtime), None) if self.checked => {
+                    Some(format!("{} runtime not found", runtime))
+                }
+                _ => None,
+            },
+        }
+    }
+}
+
+/// What a server reported during a successful `initialize` handshake
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpHealth {
+    pub server_name: Option<String>,
+    pub server_version: Option<String>,
+    pub protocol_version: Option<String>,
+    pub latency_ms: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct McpConfig {
+    #[serde(default)]
+    pub transport: McpTransport,
     pub command: Option<String>,
     pub args: Option<Vec<String>>,
     pub env: Option<HashMap<String, String>>,
 }
 
+/// How Claude Code talks to an MCP server. `command`/`args`/`env` on
+/// `McpConfig` only apply to `Stdio`; remote transports carry a URL.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum McpTransport {
+    #[default]
+    Stdio,
+    Http {
+        url: String,
+        headers: Option<HashMap<String, String>>,
+    },
+    Sse {
+        url: String,
+        headers: Option<HashMap<String, String>>,
+    },
+}
+
+impl McpTransport {
+    pub fn url(&self) -> Option<&str> {
+        match self {
+            McpTransport::Stdio => None,
+            McpTransport::Http { url, .. } | McpTransport::Sse { url, .. } => Some(url),
+        }
+    }
+
+    pub fn headers(&self) -> Option<&HashMap<String, String>> {
+        match self {
+            McpTransport::Stdio => None,
+            McpTransport::Http { headers, .. } | McpTransport::Sse { headers, .. } => {
+                headers.as_ref()
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for McpTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            McpTransport::Stdio => write!(f, "stdio"),
+            McpTransport::Http { .. } => write!(f, "http"),
+            McpTransport::Sse { .. } => write!(f, "sse"),
+        }
+    }
+}
+
+impl McpConfig {
+    /// The command for stdio servers or the URL for remote ones
+    pub fn target(&self) -> Option<&str> {
+        match self.transport {
+            McpTransport::Stdio => self.command.as_deref(),
+            _ => self.transport.url(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum SourceType {
     Local,
     Api,
+    /// Present both locally and in the API, merged by name; the item's
+    /// `local` and `api` fields hold each half
     Both,
 }
 
+/// The local half of a merged item
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LocalInfo {
+    pub path: Option<PathBuf>,
+    pub version: Option<String>,
+}
+
+/// The API half of a merged item
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ApiInfo {
+    pub id: Option<String>,
+    pub version: Option<String>,
+}
+
+impl std::fmt::Display for SourceType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SourceType::Local => write!(f, "local"),
+            SourceType::Api => write!(f, "api"),
+            SourceType::Both => write!(f, "both"),
+        }
+    }
+}
+
+/// Where a local item is configured: the user-level `~/.claude` directory,
+/// the current project checkout, a private per-project entry (`~/.claude.json`
+/// or `settings.local.json`), or enterprise managed settings.
+/// Ordered by increasing precedence.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Scope {
+    #[default]
+    User,
+    Project,
+    Local,
+    Managed,
+}
+
+impl std::str::FromStr for Scope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "user" => Ok(Scope::User),
+            "project" => Ok(Scope::Project),
+            "local" => Ok(Scope::Local),
+            "managed" => Ok(Scope::Managed),
+            _ => Err(format!(
+                "unknown scope '{}' (expected user, project, local or managed)",
+                s
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for Scope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Scope::User => write!(f, "user"),
+            Scope::Project => write!(f, "project"),
+            Scope::Local => write!(f, "local"),
+            Scope::Managed => write!(f, "managed"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum ConnectionStatus {
@@ -71,10 +431,404 @@ pub enum ConnectionStatus {
     Error(String),
 }
 
+impl std::fmt::Display for ConnectionStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConnectionStatus::Connected => write!(f, "connected"),
+            ConnectionStatus::Disconnected => write!(f, "disconnected"),
+            ConnectionStatus::Unknown => write!(f, "unknown"),
+            ConnectionStatus::Error(e) => write!(f, "error: {}", e),
+        }
+    }
+}
+
+/// A custom slash command loaded from a `commands/` directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Command {
+    /// Namespaced name, e.g. `frontend:component` for `commands/frontend/component.md`
+    pub name: String,
+    pub description: Option<String>,
+    pub argument_hint: Option<String>,
+    pub allowed_tools: Vec<String>,
+    pub model: Option<String>,
+    pub scope: Scope,
+    pub path: PathBuf,
+    #[serde(default)]
+    pub config_dir: Option<PathBuf>,
+}
+
+/// A subagent defined in an `agents/` directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Agent {
+    pub name: String,
+    pub description: Option<String>,
+    /// Tools the agent may use; empty means it inherits all tools
+    pub tools: Vec<String>,
+    pub model: Option<String>,
+    pub color: Option<String>,
+    pub scope: Scope,
+    pub path: PathBuf,
+    #[serde(default)]
+    pub config_dir: Option<PathBuf>,
+}
+
+/// A single hook command configured in a settings file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Hook {
+    /// Hook event, e.g. `PreToolUse` or `SessionStart`
+    pub event: String,
+    pub matcher: Option<String>,
+    /// Hook type, `command` or `prompt`
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Shell command, or the prompt text for prompt hooks
+    pub command: String,
+    pub timeout: Option<u64>,
+    pub scope: Scope,
+    /// Settings file the hook was read from
+    pub source: PathBuf,
+    #[serde(default)]
+    pub config_dir: Option<PathBuf>,
+}
+
+/// An installed Claude Code plugin and what it contributes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Plugin {
+    /// `name@marketplace` as used in `enabledPlugins`
+    pub id: String,
+    pub name: String,
+    pub marketplace: Option<String>,
+    /// Where the marketplace comes from, e.g. `github:owner/repo`
+    pub marketplace_source: Option<String>,
+    pub version: Option<String>,
+    pub description: Option<String>,
+    pub enabled: bool,
+    pub path: PathBuf,
+    pub skills: Vec<String>,
+    pub commands: Vec<String>,
+    pub agents: Vec<String>,
+    pub hooks: Vec<String>,
+    pub mcps: Vec<String>,
+    #[serde(default)]
+    pub config_dir: Option<PathBuf>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ClaudeList {
     pub skills: Vec<Skill>,
     pub mcps: Vec<McpServer>,
+    #[serde(default)]
+    pub commands: Vec<Command>,
+    #[serde(default)]
+    pub agents: Vec<Agent>,
+    #[serde(default)]
+    pub hooks: Vec<Hook>,
+    #[serde(default)]
+    pub plugins: Vec<Plugin>,
+    /// Problems that were skipped over while listing
+    #[serde(default)]
+    pub warnings: Vec<Warning>,
+}
+
+/// What `install` or `uninstall` did, or would do with `--dry-run`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillChange {
+    pub name: String,
+    pub scope: Scope,
+    /// The skill directory written or removed
+    pub path: PathBuf,
+    /// Files copied, relative to `path`
+    #[serde(default)]
+    pub files: Vec<PathBuf>,
+    /// An existing skill directory was overwritten
+    #[serde(default)]
+    pub replaced: bool,
+    /// Other skills with the same name, which take precedence or are shadowed
+    #[serde(default)]
+    pub collisions: Vec<Skill>,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// How much an `audit` finding matters, lowest first
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum Risk {
+    Low,
+    Medium,
+    High,
+}
+
+impl std::fmt::Display for Risk {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Risk::Low => write!(f, "low"),
+            Risk::Medium => write!(f, "medium"),
+            Risk::High => write!(f, "high"),
+        }
+    }
+}
+
+impl std::str::FromStr for Risk {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "low" => Ok(Risk::Low),
+            "medium" => Ok(Risk::Medium),
+            "high" => Ok(Risk::High),
+            _ => Err(format!(
+                "unknown risk '{}' (expected low, medium or high)",
+                s
+            )),
+        }
+    }
+}
+
+/// A check `audit` runs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditRule {
+    /// Short identifier, e.g. `pipe-to-shell`
+    pub id: String,
+    pub risk: Risk,
+    pub description: String,
+}
+
+/// Something `audit` found that Claude Code could execute unsafely
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditFinding {
+    /// `skill`, `mcp` or `hook`
+    pub kind: String,
+    /// Name of the skill or server, or the event of the hook
+    pub item: String,
+    pub risk: Risk,
+    pub rule: String,
+    pub file: Option<PathBuf>,
+    /// 1-based line in `file`, when the problem is on a specific line
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl AuditFinding {
+    /// `file:line`, just the file, or `-`
+    pub fn location(&self) -> String {
+        match (&self.file, self.line) {
+            (Some(file), Some(line)) => format!("{}:{}", file.display(), line),
+            (Some(file), None) => file.display().to_string(),
+            (None, _) => "-".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuditReport {
+    /// The checks that were run
+    pub rules: Vec<AuditRule>,
+    /// Highest risk first
+    pub findings: Vec<AuditFinding>,
+    /// Problems that were skipped over while listing
+    #[serde(default)]
+    pub warnings: Vec<Warning>,
+}
+
+impl AuditReport {
+    pub fn count(&self, risk: Risk) -> usize {
+        self.findings.iter().filter(|f| f.risk == risk).count()
+    }
+
+    /// Whether any finding is at `risk` or above
+    pub fn reaches(&self, risk: Risk) -> bool {
+        self.findings.iter().any(|f| f.risk >= risk)
+    }
+}
+
+/// An MCP server name defined in more than one file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpConflict {
+    pub name: String,
+    /// In order of precedence; the first is the one Claude Code uses
+    pub definitions: Vec<McpDefinition>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpDefinition {
+    #[serde(flatten)]
+    pub server: McpServer,
+    /// Whether this definition is the one in effect
+    pub wins: bool,
+    /// How this definition differs from the one in effect
+    pub differences: Vec<McpDifference>,
+}
+
+/// A setting in which a shadowed definition differs from the one in effect.
+/// Environment and header values are not repeated, only the names that differ.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "field", rename_all = "lowercase")]
+pub enum McpDifference {
+    Transport {
+        shadowed: String,
+        winner: String,
+    },
+    Url {
+        shadowed: Option<String>,
+        winner: Option<String>,
+    },
+    Command {
+        shadowed: Option<String>,
+        winner: Option<String>,
+    },
+    Args {
+        shadowed: Vec<String>,
+        winner: Vec<String>,
+    },
+    Env {
+        keys: Vec<String>,
+    },
+    Headers {
+        keys: Vec<String>,
+    },
+}
+
+impl std::fmt::Display for McpDifference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let or_dash = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".to_string());
+        match self {
+            McpDifference::Transport { shadowed, winner } => {
+                write!(f, "transport: {} vs {}", shadowed, winner)
+            }
+            McpDifference::Url { shadowed, winner } => {
+                write!(f, "url: {} vs {}", or_dash(shadowed), or_dash(winner))
+            }
+            McpDifference::Command { shadowed, winner } => {
+                write!(f, "command: {} vs {}", or_dash(shadowed), or_dash(winner))
+            }
+            McpDifference::Args { shadowed, winner } => {
+                write!(f, "args: {} vs {}", shadowed.join(" "), winner.join(" "))
+            }
+            McpDifference::Env { keys } => write!(f, "env: {}", keys.join(", ")),
+            McpDifference::Headers { keys } => write!(f, "headers: {}", keys.join(", ")),
+        }
+    }
+}
+
+/// An edit `mcp add/remove/rename/enable/disable` made to a config file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpChange {
+    /// `added`, `removed`, `renamed`, `enabled` or `disabled`
+    pub action: String,
+    pub name: String,
+    /// Previous name, for `renamed`
+    pub renamed_from: Option<String>,
+    pub scope: Scope,
+    pub file: PathBuf,
+    /// Copy of the file before the edit
+    pub backup: Option<PathBuf>,
+}
+
+impl ClaudeList {
+    /// Estimated tokens the listed skills' names and descriptions take up
+    pub fn skill_metadata_tokens(&self) -> usize {
+        self.skills
+            .iter()
+            .filter_map(|s| s.tokens.map(|t| t.metadata))
+            .sum()
+    }
+}
+
+/// A file or source that could not be read. Listing carries on without it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Warning {
+    /// Id of the registry source that reported it, e.g. `user`, `project`,
+    /// `plugin` or `api`
+    pub source: String,
+    pub file: Option<PathBuf>,
+    pub error: String,
+}
+
+impl Warning {
+    pub fn new(source: impl Into<String>, error: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+            file: None,
+            error: error.into(),
+        }
+    }
+
+    pub fn with_file(mut self, file: impl Into<PathBuf>) -> Self {
+        self.file = Some(file.into());
+        self
+    }
+}
+
+impl std::fmt::Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.file {
+            Some(ref file) => write!(f, "{} ({}): {}", file.display(), self.source, self.error),
+            None => write!(f, "{}: {}", self.source, self.error),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A problem `lint` found in a skill directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LintFinding {
+    pub skill: String,
+    pub severity: Severity,
+    /// Short identifier of the rule, e.g. `name-format`
+    pub rule: String,
+    pub file: PathBuf,
+    /// 1-based line in `file`, when the problem is on a specific line
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl LintFinding {
+    /// `file:line`, or just the file
+    pub fn location(&self) -> String {
+        match self.line {
+            Some(line) => format!("{}:{}", self.file.display(), line),
+            None => self.file.display().to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LintReport {
+    /// Names of the skills that were checked
+    pub skills: Vec<String>,
+    pub findings: Vec<LintFinding>,
+    /// Problems that were skipped over while listing
+    #[serde(default)]
+    pub warnings: Vec<Warning>,
+}
+
+impl LintReport {
+    pub fn count(&self, severity: Severity) -> usize {
+        self.findings
+            .iter()
+            .filter(|f| f.severity == severity)
+            .count()
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.count(Severity::Error) > 0
+    }
 }
 
 impl Skill {
@@ -83,8 +837,16 @@ impl Skill {
             name: name.into(),
             version: None,
             source: SourceType::Local,
+            scope: Scope::User,
             path: None,
             description: None,
+            plugin: None,
+            id: None,
+            local: None,
+            api: None,
+            drift: false,
+            config_dir: None,
+            tokens: None,
         }
     }
 
@@ -102,6 +864,21 @@ impl Skill {
         self.path = Some(path);
         self
     }
+
+    pub fn with_scope(mut self, scope: Scope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    pub fn with_plugin(mut self, plugin: impl Into<String>) -> Self {
+        self.plugin = Some(plugin.into());
+        self
+    }
+
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
 }
 
 impl McpServer {
@@ -111,6 +888,15 @@ impl McpServer {
             status: ConnectionStatus::Unknown,
             config: None,
             source: SourceType::Local,
+            scope: Scope::User,
+            project: None,
+            health: None,
+            plugin: None,
+            config_dir: None,
+            disabled: false,
+            unapproved: false,
+            origin: None,
+            resolution: None,
         }
     }
 
@@ -123,4 +909,29 @@ impl McpServer {
         self.source = source;
         self
     }
+
+    pub fn with_scope(mut self, scope: Scope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    pub fn with_project(mut self, project: PathBuf) -> Self {
+        self.project = Some(project);
+        self
+    }
+
+    pub fn with_config(mut self, config: McpConfig) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    pub fn with_plugin(mut self, plugin: impl Into<String>) -> Self {
+        self.plugin = Some(plugin.into());
+        self
+    }
+
+    pub fn with_origin(mut self, origin: PathBuf) -> Self {
+        self.origin = Some(origin);
+        self
+    }
 }
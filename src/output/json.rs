@@ -1,13 +1,26 @@
 use crate::error::CliError;
-use crate::model::{ClaudeList, DetailItem};
-use crate::output::{DetailFormatter, Formatter};
+use crate::model::{AuditReport, ClaudeList, DetailItem, LintReport, McpConflict};
+use crate::output::{
+    masked_conflicts, masked_detail, masked_list, AuditFormatter, ConflictFormatter,
+    DetailFormatter, Formatter, LintFormatter,
+};
 use serde_json;
 
-pub struct JsonFormatter;
+pub struct JsonFormatter {
+    show_secrets: bool,
+}
 
 impl JsonFormatter {
     pub fn new() -> Self {
-        Self
+        Self {
+            show_secrets: false,
+        }
+    }
+
+    /// Print MCP env values, headers and arguments that look like secrets as-is
+    pub fn with_show_secrets(mut self, show_secrets: bool) -> Self {
+        self.show_secrets = show_secrets;
+        self
     }
 }
 
@@ -19,22 +32,49 @@ impl Default for JsonFormatter {
 
 impl Formatter for JsonFormatter {
     fn format(&self, data: &ClaudeList) -> Result<String, CliError> {
-        let json = serde_json::to_string_pretty(data)?;
+        let data = masked_list(data, self.show_secrets);
+        let json = serde_json::to_string_pretty(&data)?;
         Ok(json)
     }
 }
 
+impl LintFormatter for JsonFormatter {
+    fn format_lint(&self, report: &LintReport) -> Result<String, CliError> {
+        Ok(serde_json::to_string_pretty(report)?)
+    }
+}
+
+impl AuditFormatter for JsonFormatter {
+    fn format_audit(&self, report: &AuditReport) -> Result<String, CliError> {
+        Ok(serde_json::to_string_pretty(report)?)
+    }
+}
+
+impl ConflictFormatter for JsonFormatter {
+    fn format_conflicts(&self, conflicts: &[McpConflict]) -> Result<String, CliError> {
+        let conflicts = masked_conflicts(conflicts, self.show_secrets);
+        Ok(serde_json::to_string_pretty(&conflicts)?)
+    }
+}
+
 impl DetailFormatter for JsonFormatter {
     fn format_detail(&self, item: &DetailItem, raw: bool) -> Result<String, CliError> {
+        let item = masked_detail(item, self.show_secrets);
+        let item = item.as_ref();
         if raw {
             // For raw mode with JSON formatter, we still return JSON but add raw_content field
             let mut value = serde_json::to_value(item)?;
 
-            // If it's a skill, add raw content
-            if let DetailItem::Skill(skill) = item {
-                let raw_content = std::fs::read_to_string(&skill.path)?;
+            // If it's a skill or agent, add raw content
+            let raw_source = match item {
+                DetailItem::Skill(skill) => Some(("Skill", &skill.path)),
+                DetailItem::Agent(agent) => Some(("Agent", &agent.agent.path)),
+                DetailItem::Mcp(_) => None,
+            };
+            if let Some((key, path)) = raw_source {
+                let raw_content = std::fs::read_to_string(path)?;
                 if let Some(obj) = value.as_object_mut() {
-                    match obj.get_mut("Skill") {
+                    match obj.get_mut(key) {
                         Some(skill_obj) => {
                             if let Some(so) = skill_obj.as_object_mut() {
                                 so.insert(
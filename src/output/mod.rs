@@ -1,7 +1,10 @@
 use crate::error::CliError;
-use crate::model::{ClaudeList, DetailItem};
+use crate::model::{AuditReport, ClaudeList, DetailItem, LintReport, McpConflict, McpDifference};
+use crate::secrets::{mask_args, mask_config, mask_url};
+use std::borrow::Cow;
 
 pub mod json;
+pub mod sarif;
 pub mod table;
 
 pub trait Formatter {
@@ -12,5 +15,68 @@ pub trait DetailFormatter {
     fn format_detail(&self, item: &DetailItem, raw: bool) -> Result<String, CliError>;
 }
 
+pub trait LintFormatter {
+    fn format_lint(&self, report: &LintReport) -> Result<String, CliError>;
+}
+
+pub trait AuditFormatter {
+    fn format_audit(&self, report: &AuditReport) -> Result<String, CliError>;
+}
+
+pub trait ConflictFormatter {
+    fn format_conflicts(&self, conflicts: &[McpConflict]) -> Result<String, CliError>;
+}
+
+/// `data` with MCP secrets masked, unless they are to be shown
+fn masked_list(data: &ClaudeList, show_secrets: bool) -> Cow<'_, ClaudeList> {
+    if show_secrets {
+        return Cow::Borrowed(data);
+    }
+    let mut data = data.clone();
+    for config in data.mcps.iter_mut().filter_map(|m| m.config.as_mut()) {
+        mask_config(config);
+    }
+    Cow::Owned(data)
+}
+
+fn masked_detail(item: &DetailItem, show_secrets: bool) -> Cow<'_, DetailItem> {
+    match item {
+        DetailItem::Mcp(mcp) if !show_secrets => {
+            let mut mcp = mcp.clone();
+            mask_config(&mut mcp.config);
+            Cow::Owned(DetailItem::Mcp(mcp))
+        }
+        _ => Cow::Borrowed(item),
+    }
+}
+
+fn masked_conflicts(conflicts: &[McpConflict], show_secrets: bool) -> Cow<'_, [McpConflict]> {
+    if show_secrets {
+        return Cow::Borrowed(conflicts);
+    }
+    let mut conflicts = conflicts.to_vec();
+    for definition in conflicts.iter_mut().flat_map(|c| c.definitions.iter_mut()) {
+        if let Some(ref mut config) = definition.server.config {
+            mask_config(config);
+        }
+        for difference in definition.differences.iter_mut() {
+            match difference {
+                McpDifference::Url { shadowed, winner } => {
+                    for url in shadowed.iter_mut().chain(winner.iter_mut()) {
+                        *url = mask_url(url);
+                    }
+                }
+                McpDifference::Args { shadowed, winner } => {
+                    *shadowed = mask_args(shadowed);
+                    *winner = mask_args(winner);
+                }
+                _ => {}
+            }
+        }
+    }
+    Cow::Owned(conflicts)
+}
+
 pub use json::JsonFormatter;
+pub use sarif::SarifFormatter;
 pub use table::TableFormatter;
@@ -0,0 +1,88 @@
+use crate::error::CliError;
+use crate::model::{AuditReport, Risk};
+use crate::output::AuditFormatter;
+use serde_json::{json, Value};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// SARIF 2.1.0, for code scanning dashboards
+pub struct SarifFormatter;
+
+impl SarifFormatter {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for SarifFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AuditFormatter for SarifFormatter {
+    fn format_audit(&self, report: &AuditReport) -> Result<String, CliError> {
+        let rules: Vec<Value> = report
+            .rules
+            .iter()
+            .map(|rule| {
+                json!({
+                    "id": rule.id,
+                    "shortDescription": { "text": rule.description },
+                    "defaultConfiguration": { "level": level(rule.risk) },
+                })
+            })
+            .collect();
+
+        let results: Vec<Value> = report
+            .findings
+            .iter()
+            .map(|finding| {
+                let mut result = json!({
+                    "ruleId": finding.rule,
+                    "level": level(finding.risk),
+                    "message": {
+                        "text": format!("{} '{}': {}", finding.kind, finding.item, finding.message)
+                    },
+                    "properties": { "kind": finding.kind, "item": finding.item, "risk": finding.risk },
+                });
+                if let Some(ref file) = finding.file {
+                    let mut location = json!({
+                        "physicalLocation": {
+                            "artifactLocation": { "uri": format!("file://{}", file.display()) },
+                        }
+                    });
+                    if let Some(line) = finding.line {
+                        location["physicalLocation"]["region"] = json!({ "startLine": line });
+                    }
+                    result["locations"] = json!([location]);
+                }
+                result
+            })
+            .collect();
+
+        let sarif = json!({
+            "$schema": SARIF_SCHEMA,
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": env!("CARGO_PKG_NAME"),
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "rules": rules,
+                    }
+                },
+                "results": results,
+            }],
+        });
+        Ok(serde_json::to_string_pretty(&sarif)?)
+    }
+}
+
+fn level(risk: Risk) -> &'static str {
+    match risk {
+        Risk::Low => "note",
+        Risk::Medium => "warning",
+        Risk::High => "error",
+    }
+}
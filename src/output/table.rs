@@ -1,14 +1,25 @@
 use crate::error::CliError;
-use crate::model::{ClaudeList, DetailItem, McpDetail, SkillDetail};
-use crate::output::{DetailFormatter, Formatter};
+use crate::model::{
+    AgentDetail, AuditReport, ClaudeList, DetailItem, LintReport, McpCapabilities, McpConflict,
+    McpDetail, Risk, Severity, Skill, SkillDetail, SourceType,
+};
+use crate::output::{
+    masked_conflicts, masked_detail, masked_list, AuditFormatter, ConflictFormatter,
+    DetailFormatter, Formatter, LintFormatter,
+};
+use std::path::PathBuf;
 
 pub struct TableFormatter {
     verbose: bool,
+    show_secrets: bool,
 }
 
 impl TableFormatter {
     pub fn new() -> Self {
-        Self { verbose: false }
+        Self {
+            verbose: false,
+            show_secrets: false,
+        }
     }
 
     pub fn with_verbose(mut self, verbose: bool) -> Self {
@@ -16,6 +27,12 @@ impl TableFormatter {
         self
     }
 
+    /// Print MCP env values, headers and arguments that look like secrets as-is
+    pub fn with_show_secrets(mut self, show_secrets: bool) -> Self {
+        self.show_secrets = show_secrets;
+        self
+    }
+
     fn format_table(&self, headers: &[&str], rows: &[Vec<String>]) -> String {
         if rows.is_empty() {
             return String::new();
@@ -84,29 +101,93 @@ impl TableFormatter {
     }
 }
 
+/// Truncate unicode strings safely
+fn truncate(s: &str, max_chars: usize) -> String {
+    if s.chars().count() <= max_chars {
+        s.to_string()
+    } else {
+        let truncated: String = s.chars().take(max_chars.saturating_sub(3)).collect();
+        format!("{}...", truncated)
+    }
+}
+
+/// Byte counts as `512 B`, `1.5 KB` or `2.0 MB`
+fn format_size(bytes: u64) -> String {
+    const KB: f64 = 1024.0;
+    let size = bytes as f64;
+    if size < KB {
+        format!("{} B", bytes)
+    } else if size < KB * KB {
+        format!("{:.1} KB", size / KB)
+    } else {
+        format!("{:.1} MB", size / (KB * KB))
+    }
+}
+
+/// `both (drift)` marks uploaded skills whose local copy has changed
+fn skill_source(skill: &Skill) -> String {
+    if skill.drift {
+        format!("{} (drift)", skill.source)
+    } else {
+        skill.source.to_string()
+    }
+}
+
+/// Merged skills show the local and the uploaded version
+fn skill_version(skill: &Skill) -> String {
+    match (&skill.local, &skill.api) {
+        (Some(local), Some(api)) => format!(
+            "{} / {}",
+            local.version.as_deref().unwrap_or("-"),
+            api.version.as_deref().unwrap_or("-")
+        ),
+        _ => skill.version.clone().unwrap_or_else(|| "-".to_string()),
+    }
+}
+
+/// Append a `Config` column when items were read from several config directories
+fn push_config_column<T>(
+    headers: &mut Vec<&str>,
+    rows: &mut [Vec<String>],
+    items: &[T],
+    config_dir: impl Fn(&T) -> Option<&PathBuf>,
+) {
+    if !items.iter().any(|item| config_dir(item).is_some()) {
+        return;
+    }
+    headers.push("Config");
+    for (row, item) in rows.iter_mut().zip(items) {
+        row.push(
+            config_dir(item)
+                .map(|dir| dir.display().to_string())
+                .unwrap_or_else(|| "-".to_string()),
+        );
+    }
+}
+
+/// Scope only applies to local skills
+fn skill_scope(skill: &Skill) -> String {
+    match skill.source {
+        SourceType::Api => "-".to_string(),
+        _ => skill.scope.to_string(),
+    }
+}
+
 impl Formatter for TableFormatter {
     fn format(&self, data: &ClaudeList) -> Result<String, CliError> {
+        let data = masked_list(data, self.show_secrets);
+        let data = data.as_ref();
         let mut output = String::new();
 
         // Skills section
         if !data.skills.is_empty() {
             output.push_str("Skills:\n");
 
-            let headers: Vec<&str>;
-            let rows: Vec<Vec<String>>;
-
-            // Helper function to truncate unicode strings safely
-            fn truncate(s: &str, max_chars: usize) -> String {
-                if s.chars().count() <= max_chars {
-                    s.to_string()
-                } else {
-                    let truncated: String = s.chars().take(max_chars.saturating_sub(3)).collect();
-                    format!("{}...", truncated)
-                }
-            }
+            let mut headers: Vec<&str>;
+            let mut rows: Vec<Vec<String>>;
 
             if self.verbose {
-                headers = vec!["Name", "Version", "Source", "Description"];
+                headers = vec!["Name", "Version", "Source", "Scope", "Description"];
                 rows = data
                     .skills
                     .iter()
@@ -115,8 +196,9 @@ impl Formatter for TableFormatter {
                         let desc = truncate(description, 50);
                         vec![
                             s.name.clone(),
-                            s.version.clone().unwrap_or_else(|| "-".to_string()),
-                            format!("{:?}", s.source).to_lowercase(),
+                            skill_version(s),
+                            skill_source(s),
+                            skill_scope(s),
                             desc,
                         ]
                     })
@@ -126,67 +208,368 @@ impl Formatter for TableFormatter {
                 let has_descriptions = data.skills.iter().any(|s| s.description.is_some());
 
                 if has_descriptions {
-                    headers = vec!["Name", "Description"];
+                    headers = vec!["Name", "Scope", "Description"];
                     rows = data
                         .skills
                         .iter()
                         .map(|s| {
                             let description = s.description.as_deref().unwrap_or("-");
                             let desc = truncate(description, 60);
-                            vec![s.name.clone(), desc]
+                            vec![s.name.clone(), skill_scope(s), desc]
                         })
                         .collect();
                 } else {
-                    headers = vec!["Name"];
-                    rows = data.skills.iter().map(|s| vec![s.name.clone()]).collect();
+                    headers = vec!["Name", "Scope"];
+                    rows = data
+                        .skills
+                        .iter()
+                        .map(|s| vec![s.name.clone(), skill_scope(s)])
+                        .collect();
+                }
+            }
+
+            // Show where each skill comes from once the API is involved
+            if !self.verbose && data.skills.iter().any(|s| s.source != SourceType::Local) {
+                headers.insert(2, "Source");
+                for (row, skill) in rows.iter_mut().zip(&data.skills) {
+                    row.insert(2, skill_source(skill));
+                }
+            }
+
+            // Only show the plugin column when plugins contribute skills
+            if data.skills.iter().any(|s| s.plugin.is_some()) {
+                headers.push("Plugin");
+                for (row, skill) in rows.iter_mut().zip(&data.skills) {
+                    row.push(skill.plugin.clone().unwrap_or_else(|| "-".to_string()));
+                }
+            }
+
+            // Estimated context cost, as metadata / body / referenced files
+            if self.verbose && data.skills.iter().any(|s| s.tokens.is_some()) {
+                headers.extend(["Meta Tokens", "Body Tokens", "Ref Tokens"]);
+                for (row, skill) in rows.iter_mut().zip(&data.skills) {
+                    let tokens = skill.tokens.unwrap_or_default();
+                    let count = |n: Option<usize>| n.map_or("-".to_string(), |n| n.to_string());
+                    row.push(tokens.metadata.to_string());
+                    row.push(count(tokens.body));
+                    row.push(count(tokens.references));
                 }
             }
 
+            push_config_column(&mut headers, &mut rows, &data.skills, |s| {
+                s.config_dir.as_ref()
+            });
             output.push_str(&self.format_table(&headers, &rows));
             output.push_str("\n\n");
+
+            if self.verbose {
+                output.push_str(&format!(
+                    "Skill metadata budget: ~{} tokens across {} skills\n\n",
+                    data.skill_metadata_tokens(),
+                    data.skills.len()
+                ));
+            }
         }
 
         // MCP section
         if !data.mcps.is_empty() {
             output.push_str("MCP Servers:\n");
 
-            let headers: Vec<&str>;
-            let rows: Vec<Vec<String>>;
-
+            let mut headers = vec!["Name", "Scope", "Status", "Transport"];
             if self.verbose {
-                headers = vec!["Name", "Status", "Command"];
-                rows = data
-                    .mcps
-                    .iter()
-                    .map(|m| {
-                        let status = format!("{:?}", m.status).to_lowercase();
-                        let cmd = m
+                headers.push("Command / URL");
+            }
+
+            // Only show the project column when per-project servers are present
+            let has_projects = data.mcps.iter().any(|m| m.project.is_some());
+            if has_projects {
+                headers.push("Project");
+            }
+
+            let has_plugins = data.mcps.iter().any(|m| m.plugin.is_some());
+            if has_plugins {
+                headers.push("Plugin");
+            }
+
+            // Health check columns
+            let has_health = data.mcps.iter().any(|m| m.health.is_some());
+            if has_health {
+                headers.extend(["Latency", "Server", "Protocol"]);
+            }
+
+            // `--doctor` column
+            let has_resolution = data.mcps.iter().any(|m| m.resolution.is_some());
+            if has_resolution {
+                headers.push("Doctor");
+            }
+
+            let mut rows: Vec<Vec<String>> = data
+                .mcps
+                .iter()
+                .map(|m| {
+                    // Claude Code does not start disabled servers
+                    let status = if m.disabled {
+                        "disabled".to_string()
+                    } else {
+                        m.status.to_string()
+                    };
+                    let transport = m
+                        .config
+                        .as_ref()
+                        .map(|c| c.transport.to_string())
+                        .unwrap_or_else(|| "-".to_string());
+                    let mut row = vec![m.name.clone(), m.scope.to_string(), status, transport];
+                    if self.verbose {
+                        let target = m
                             .config
                             .as_ref()
-                            .and_then(|c| c.command.as_deref())
+                            .and_then(|c| c.target())
                             .unwrap_or("-")
                             .to_string();
-                        vec![m.name.clone(), status, cmd]
-                    })
-                    .collect();
-            } else {
-                headers = vec!["Name", "Status"];
-                rows = data
-                    .mcps
-                    .iter()
-                    .map(|m| {
-                        let status = format!("{:?}", m.status).to_lowercase();
-                        vec![m.name.clone(), status]
-                    })
-                    .collect();
+                        row.push(target);
+                    }
+                    if has_projects {
+                        let project = m
+                            .project
+                            .as_ref()
+                            .map(|p| p.display().to_string())
+                            .unwrap_or_else(|| "-".to_string());
+                        row.push(project);
+                    }
+                    if has_plugins {
+                        row.push(m.plugin.clone().unwrap_or_else(|| "-".to_string()));
+                    }
+                    if has_health {
+                        match m.health {
+                            Some(ref h) => {
+                                let server = match (&h.server_name, &h.server_version) {
+                                    (Some(n), Some(v)) => format!("{} {}", n, v),
+                                    (Some(n), None) => n.clone(),
+                                    _ => "-".to_string(),
+                                };
+                                row.push(format!("{}ms", h.latency_ms));
+                                row.push(server);
+                                row.push(h.protocol_version.clone().unwrap_or_else(|| "-".into()));
+                            }
+                            None => row.extend(vec!["-".to_string(); 3]),
+                        }
+                    }
+                    if has_resolution {
+                        let doctor = match m.resolution {
+                            Some(ref r) => r.problem().unwrap_or_else(|| {
+                                let path = r
+                                    .path
+                                    .as_ref()
+                                    .map(|p| p.display().to_string())
+                                    .unwrap_or_default();
+                                match r.runtime_version {
+                                    Some(ref version) => format!("{} ({})", path, version),
+                                    None => path,
+                                }
+                            }),
+                            None => "-".to_string(),
+                        };
+                        row.push(doctor);
+                    }
+                    row
+                })
+                .collect();
+
+            push_config_column(&mut headers, &mut rows, &data.mcps, |m| {
+                m.config_dir.as_ref()
+            });
+            output.push_str(&self.format_table(&headers, &rows));
+            output.push_str("\n\n");
+        }
+
+        // Commands section
+        if !data.commands.is_empty() {
+            output.push_str("Commands:\n");
+
+            let mut headers = vec!["Name", "Scope", "Description"];
+            if self.verbose {
+                headers.extend(["Arguments", "Model", "Allowed Tools"]);
+            }
+
+            let mut rows: Vec<Vec<String>> = data
+                .commands
+                .iter()
+                .map(|c| {
+                    let description = c.description.as_deref().unwrap_or("-");
+                    let mut row = vec![
+                        format!("/{}", c.name),
+                        c.scope.to_string(),
+                        truncate(description, if self.verbose { 40 } else { 60 }),
+                    ];
+                    if self.verbose {
+                        row.push(c.argument_hint.clone().unwrap_or_else(|| "-".into()));
+                        row.push(c.model.clone().unwrap_or_else(|| "-".into()));
+                        let tools = if c.allowed_tools.is_empty() {
+                            "-".to_string()
+                        } else {
+                            truncate(&c.allowed_tools.join(", "), 40)
+                        };
+                        row.push(tools);
+                    }
+                    row
+                })
+                .collect();
+
+            push_config_column(&mut headers, &mut rows, &data.commands, |c| {
+                c.config_dir.as_ref()
+            });
+            output.push_str(&self.format_table(&headers, &rows));
+            output.push_str("\n\n");
+        }
+
+        // Agents section
+        if !data.agents.is_empty() {
+            output.push_str("Agents:\n");
+
+            let mut headers = vec!["Name", "Scope", "Description"];
+            if self.verbose {
+                headers.extend(["Tools", "Model", "Color"]);
             }
 
+            let mut rows: Vec<Vec<String>> = data
+                .agents
+                .iter()
+                .map(|a| {
+                    let description = a.description.as_deref().unwrap_or("-");
+                    let mut row = vec![
+                        a.name.clone(),
+                        a.scope.to_string(),
+                        truncate(description, if self.verbose { 40 } else { 60 }),
+                    ];
+                    if self.verbose {
+                        let tools = if a.tools.is_empty() {
+                            "all".to_string()
+                        } else {
+                            truncate(&a.tools.join(", "), 40)
+                        };
+                        row.push(tools);
+                        row.push(a.model.clone().unwrap_or_else(|| "inherit".into()));
+                        row.push(a.color.clone().unwrap_or_else(|| "-".into()));
+                    }
+                    row
+                })
+                .collect();
+
+            push_config_column(&mut headers, &mut rows, &data.agents, |a| {
+                a.config_dir.as_ref()
+            });
             output.push_str(&self.format_table(&headers, &rows));
+            output.push_str("\n\n");
+        }
+
+        // Hooks section
+        if !data.hooks.is_empty() {
+            output.push_str("Hooks:\n");
+
+            let mut headers = vec!["Event", "Matcher", "Command", "Timeout", "Scope"];
+            if self.verbose {
+                headers.extend(["Type", "File"]);
+            }
+
+            let mut rows: Vec<Vec<String>> = data
+                .hooks
+                .iter()
+                .map(|h| {
+                    let mut row = vec![
+                        h.event.clone(),
+                        h.matcher.clone().unwrap_or_else(|| "*".into()),
+                        if self.verbose {
+                            h.command.clone()
+                        } else {
+                            truncate(&h.command, 50)
+                        },
+                        h.timeout
+                            .map(|t| format!("{}s", t))
+                            .unwrap_or_else(|| "-".into()),
+                        h.scope.to_string(),
+                    ];
+                    if self.verbose {
+                        row.push(h.kind.clone());
+                        row.push(h.source.display().to_string());
+                    }
+                    row
+                })
+                .collect();
+
+            push_config_column(&mut headers, &mut rows, &data.hooks, |h| {
+                h.config_dir.as_ref()
+            });
+            output.push_str(&self.format_table(&headers, &rows));
+            output.push_str("\n\n");
+        }
+
+        // Plugins section
+        if !data.plugins.is_empty() {
+            output.push_str("Plugins:\n");
+
+            let mut headers = vec![
+                "Name",
+                "Marketplace",
+                "Version",
+                "Enabled",
+                "Skills",
+                "Commands",
+                "Agents",
+                "Hooks",
+                "MCPs",
+            ];
+            if self.verbose {
+                headers.extend(["Source", "Description"]);
+            }
+
+            // Counts by default, names in verbose mode
+            let contributions = |items: &[String]| {
+                if self.verbose && !items.is_empty() {
+                    truncate(&items.join(", "), 30)
+                } else {
+                    items.len().to_string()
+                }
+            };
+
+            let mut rows: Vec<Vec<String>> = data
+                .plugins
+                .iter()
+                .map(|p| {
+                    let mut row = vec![
+                        p.name.clone(),
+                        p.marketplace.clone().unwrap_or_else(|| "-".into()),
+                        p.version.clone().unwrap_or_else(|| "-".into()),
+                        if p.enabled { "yes" } else { "no" }.to_string(),
+                        contributions(&p.skills),
+                        contributions(&p.commands),
+                        contributions(&p.agents),
+                        contributions(&p.hooks),
+                        contributions(&p.mcps),
+                    ];
+                    if self.verbose {
+                        row.push(p.marketplace_source.clone().unwrap_or_else(|| "-".into()));
+                        row.push(truncate(p.description.as_deref().unwrap_or("-"), 40));
+                    }
+                    row
+                })
+                .collect();
+
+            push_config_column(&mut headers, &mut rows, &data.plugins, |p| {
+                p.config_dir.as_ref()
+            });
+            output.push_str(&self.format_table(&headers, &rows));
+            output.push_str("\n\n");
         }
 
-        // Summary if both are empty
-        if data.skills.is_empty() && data.mcps.is_empty() {
-            output.push_str("No skills or MCP servers found.\n");
+        // Summary if everything is empty
+        if data.skills.is_empty()
+            && data.mcps.is_empty()
+            && data.commands.is_empty()
+            && data.agents.is_empty()
+            && data.hooks.is_empty()
+            && data.plugins.is_empty()
+        {
+            output.push_str("No skills, MCP servers, commands, agents, hooks or plugins found.\n");
             output.push_str("Make sure Claude Code is installed and configured.");
         }
 
@@ -196,16 +579,148 @@ impl Formatter for TableFormatter {
 
 impl DetailFormatter for TableFormatter {
     fn format_detail(&self, item: &DetailItem, raw: bool) -> Result<String, CliError> {
-        match item {
+        match masked_detail(item, self.show_secrets).as_ref() {
             DetailItem::Skill(skill) => self.format_skill_detail(skill, raw),
             DetailItem::Mcp(mcp) => self.format_mcp_detail(mcp),
+            DetailItem::Agent(agent) => self.format_agent_detail(agent, raw),
+        }
+    }
+}
+
+impl LintFormatter for TableFormatter {
+    fn format_lint(&self, report: &LintReport) -> Result<String, CliError> {
+        let mut output = String::new();
+
+        if !report.findings.is_empty() {
+            let rows: Vec<Vec<String>> = report
+                .findings
+                .iter()
+                .map(|f| {
+                    vec![
+                        f.severity.to_string(),
+                        f.skill.clone(),
+                        f.location(),
+                        f.rule.clone(),
+                        f.message.clone(),
+                    ]
+                })
+                .collect();
+            output.push_str(
+                &self.format_table(&["Severity", "Skill", "Location", "Rule", "Message"], &rows),
+            );
+            output.push_str("\n\n");
+        }
+
+        let plural =
+            |n: usize, word: &str| format!("{} {}{}", n, word, if n == 1 { "" } else { "s" });
+        output.push_str(&format!(
+            "Checked {}: {}, {}",
+            plural(report.skills.len(), "skill"),
+            plural(report.count(Severity::Error), "error"),
+            plural(report.count(Severity::Warning), "warning")
+        ));
+        Ok(output)
+    }
+}
+
+impl AuditFormatter for TableFormatter {
+    fn format_audit(&self, report: &AuditReport) -> Result<String, CliError> {
+        let mut output = String::new();
+
+        if !report.findings.is_empty() {
+            let rows: Vec<Vec<String>> = report
+                .findings
+                .iter()
+                .map(|f| {
+                    vec![
+                        f.risk.to_string(),
+                        f.kind.clone(),
+                        f.item.clone(),
+                        f.location(),
+                        f.rule.clone(),
+                        f.message.clone(),
+                    ]
+                })
+                .collect();
+            output.push_str(&self.format_table(
+                &["Risk", "Kind", "Item", "Location", "Rule", "Message"],
+                &rows,
+            ));
+            output.push_str("\n\n");
+        }
+
+        output.push_str(&format!(
+            "{} findings: {} high, {} medium, {} low",
+            report.findings.len(),
+            report.count(Risk::High),
+            report.count(Risk::Medium),
+            report.count(Risk::Low)
+        ));
+        Ok(output)
+    }
+}
+
+impl ConflictFormatter for TableFormatter {
+    fn format_conflicts(&self, conflicts: &[McpConflict]) -> Result<String, CliError> {
+        if conflicts.is_empty() {
+            return Ok("No MCP server is defined in more than one file.".to_string());
+        }
+        let conflicts = masked_conflicts(conflicts, self.show_secrets);
+
+        let has_config_dirs = conflicts
+            .iter()
+            .flat_map(|c| &c.definitions)
+            .any(|d| d.server.config_dir.is_some());
+        let mut headers = vec!["Name", "Scope", "File", "Used", "Differences"];
+        if has_config_dirs {
+            headers.push("Config");
         }
+
+        let rows: Vec<Vec<String>> = conflicts
+            .iter()
+            .flat_map(|c| &c.definitions)
+            .map(|d| {
+                let file = d
+                    .server
+                    .origin
+                    .as_ref()
+                    .map_or_else(|| "-".to_string(), |p| p.display().to_string());
+                let differences = if d.wins {
+                    "-".to_string()
+                } else if d.differences.is_empty() {
+                    "identical".to_string()
+                } else {
+                    let differences: Vec<String> =
+                        d.differences.iter().map(ToString::to_string).collect();
+                    differences.join("; ")
+                };
+                let mut row = vec![
+                    d.server.name.clone(),
+                    d.server.scope.to_string(),
+                    file,
+                    if d.wins { "yes" } else { "shadowed" }.to_string(),
+                    differences,
+                ];
+                if has_config_dirs {
+                    row.push(
+                        d.server
+                            .config_dir
+                            .as_ref()
+                            .map_or_else(|| "-".to_string(), |p| p.display().to_string()),
+                    );
+                }
+                row
+            })
+            .collect();
+
+        Ok(self.format_table(&headers, &rows))
     }
 }
 
 impl TableFormatter {
     fn format_skill_detail(&self, skill: &SkillDetail, raw: bool) -> Result<String, CliError> {
-        if raw {
+        // API skills have no local file to print
+        if raw && skill.source != SourceType::Api {
             // Raw mode: read and return entire file content
             let content = std::fs::read_to_string(&skill.path)?;
             return Ok(content);
@@ -226,12 +741,108 @@ impl TableFormatter {
         }
 
         // Content
-        output.push_str("## Content\n\n");
-        output.push_str(&skill.content);
-        output.push('\n');
+        if skill.source != SourceType::Api {
+            output.push_str("## Content\n\n");
+            output.push_str(&skill.content);
+            output.push('\n');
+        }
+
+        if !skill.files.is_empty() {
+            let rows: Vec<Vec<String>> = skill
+                .files
+                .iter()
+                .map(|file| {
+                    vec![
+                        file.path.display().to_string(),
+                        file.kind.to_string(),
+                        format_size(file.size),
+                        if file.executable { "yes" } else { "-" }.to_string(),
+                    ]
+                })
+                .collect();
+            output.push_str("\n## Files\n\n");
+            output.push_str(&self.format_table(&["Path", "Type", "Size", "Executable"], &rows));
+            output.push('\n');
+        }
 
         // Metadata footer
-        output.push_str(&format!("\n---\nPath: {}\n", skill.path.display()));
+        output.push_str("\n---\n");
+        if skill.source == SourceType::Api {
+            output.push_str("Source: api\n");
+            if let Some(ref id) = skill.id {
+                output.push_str(&format!("ID: {}\n", id));
+            }
+            if let Some(ref version) = skill.version {
+                output.push_str(&format!("Version: {}\n", version));
+            }
+            if !skill.path.as_os_str().is_empty() {
+                output.push_str(&format!("Directory: {}\n", skill.path.display()));
+            }
+        } else {
+            output.push_str(&format!("Path: {}\n", skill.path.display()));
+            output.push_str(&format!("Scope: {}\n", skill.scope));
+            let start_matter = &skill.start_matter;
+            if let Some(ref version) = start_matter.version {
+                output.push_str(&format!("Version: {}\n", version));
+            }
+            if !start_matter.allowed_tools.is_empty() {
+                output.push_str(&format!(
+                    "Allowed tools: {}\n",
+                    start_matter.allowed_tools.join(", ")
+                ));
+            }
+            if let Some(ref model) = start_matter.model {
+                output.push_str(&format!("Model: {}\n", model));
+            }
+            if let Some(ref license) = start_matter.license {
+                output.push_str(&format!("License: {}\n", license));
+            }
+        }
+
+        Ok(output)
+    }
+
+    fn format_agent_detail(&self, detail: &AgentDetail, raw: bool) -> Result<String, CliError> {
+        let agent = &detail.agent;
+        if raw {
+            let content = std::fs::read_to_string(&agent.path)?;
+            return Ok(content);
+        }
+
+        let mut output = String::new();
+
+        output.push_str(&format!("Agent: {}\n", agent.name));
+        output.push_str(&"=".repeat(agent.name.len() + 7));
+        output.push_str("\n\n");
+
+        if let Some(ref description) = agent.description {
+            output.push_str("## Description\n\n");
+            output.push_str(description);
+            output.push_str("\n\n");
+        }
+
+        output.push_str("## Configuration\n\n");
+        let tools = if agent.tools.is_empty() {
+            "all (inherited)".to_string()
+        } else {
+            agent.tools.join(", ")
+        };
+        output.push_str(&format!("Tools: {}\n", tools));
+        output.push_str(&format!(
+            "Model: {}\n",
+            agent.model.as_deref().unwrap_or("inherit")
+        ));
+        if let Some(ref color) = agent.color {
+            output.push_str(&format!("Color: {}\n", color));
+        }
+        output.push('\n');
+
+        output.push_str("## System Prompt\n\n");
+        output.push_str(&detail.content);
+        output.push('\n');
+
+        output.push_str(&format!("\n---\nPath: {}\n", agent.path.display()));
+        output.push_str(&format!("Scope: {}\n", agent.scope));
 
         Ok(output)
     }
@@ -244,10 +855,45 @@ impl TableFormatter {
         output.push_str("\n\n");
 
         output.push_str(&format!("Source: {}\n", mcp.source_type));
-        output.push_str(&format!("Config Path: {}\n\n", mcp.source_path.display()));
+        output.push_str(&format!("Scope: {}\n", mcp.scope));
+        if let Some(ref project) = mcp.project {
+            output.push_str(&format!("Project: {}\n", project.display()));
+        }
+        output.push_str(&format!("Config Path: {}\n", mcp.source_path.display()));
+        output.push_str(&format!("Status: {}\n\n", mcp.status));
+
+        if let Some(ref health) = mcp.health {
+            output.push_str("## Server\n\n");
+            if let Some(ref name) = health.server_name {
+                output.push_str(&format!("Name: {}\n", name));
+            }
+            if let Some(ref version) = health.server_version {
+                output.push_str(&format!("Version: {}\n", version));
+            }
+            if let Some(ref protocol) = health.protocol_version {
+                output.push_str(&format!("Protocol: {}\n", protocol));
+            }
+            output.push_str(&format!("Latency: {}ms\n\n", health.latency_ms));
+        }
 
         output.push_str("## Configuration\n\n");
 
+        output.push_str(&format!("Transport: {}\n\n", mcp.config.transport));
+
+        if let Some(url) = mcp.config.transport.url() {
+            output.push_str(&format!("URL: `{}`\n\n", url));
+        }
+
+        if let Some(headers) = mcp.config.transport.headers() {
+            if !headers.is_empty() {
+                output.push_str("Headers:\n");
+                for (key, value) in headers {
+                    output.push_str(&format!("  - `{}`: `{}`\n", key, value));
+                }
+                output.push('\n');
+            }
+        }
+
         if let Some(ref cmd) = mcp.config.command {
             output.push_str(&format!("Command: `{}`\n\n", cmd));
         }
@@ -272,6 +918,89 @@ impl TableFormatter {
             }
         }
 
+        if let Some(ref resolution) = mcp.resolution {
+            output.push_str("## Command\n\n");
+            match resolution.path {
+                Some(ref path) => {
+                    output.push_str(&format!("Resolved: {}\n", path.display()));
+                    let executable = if resolution.executable { "yes" } else { "no" };
+                    output.push_str(&format!("Executable: {}\n", executable));
+                }
+                None => output.push_str("Resolved: not found\n"),
+            }
+            if let Some(ref runtime) = resolution.runtime {
+                let version = match resolution.runtime_version {
+                    Some(ref version) => version.as_str(),
+                    None if resolution.checked => "not found",
+                    None => "not run: found outside the trusted PATH",
+                };
+                output.push_str(&format!("Runtime: {} ({})\n", runtime, version));
+            }
+            output.push('\n');
+        }
+
+        if let Some(ref capabilities) = mcp.capabilities {
+            output.push_str(&self.format_mcp_capabilities(capabilities)?);
+        }
+
+        Ok(output)
+    }
+
+    fn format_mcp_capabilities(&self, capabilities: &McpCapabilities) -> Result<String, CliError> {
+        let mut output = String::new();
+
+        output.push_str(&format!("## Tools ({})\n\n", capabilities.tools.len()));
+        for tool in &capabilities.tools {
+            output.push_str(&format!("### {}\n\n", tool.name));
+            if let Some(ref description) = tool.description {
+                output.push_str(description.trim());
+                output.push_str("\n\n");
+            }
+            if let Some(ref schema) = tool.input_schema {
+                output.push_str("Input schema:\n\n```json\n");
+                output.push_str(&serde_json::to_string_pretty(schema)?);
+                output.push_str("\n```\n\n");
+            }
+        }
+
+        output.push_str(&format!(
+            "## Resources ({})\n\n",
+            capabilities.resources.len()
+        ));
+        for resource in &capabilities.resources {
+            output.push_str(&format!("  - `{}`", resource.uri));
+            if let Some(ref name) = resource.name {
+                output.push_str(&format!(" {}", name));
+            }
+            if let Some(ref mime_type) = resource.mime_type {
+                output.push_str(&format!(" ({})", mime_type));
+            }
+            if let Some(ref description) = resource.description {
+                output.push_str(&format!(": {}", description.trim()));
+            }
+            output.push('\n');
+        }
+        if !capabilities.resources.is_empty() {
+            output.push('\n');
+        }
+
+        output.push_str(&format!("## Prompts ({})\n\n", capabilities.prompts.len()));
+        for prompt in &capabilities.prompts {
+            output.push_str(&format!("  - `{}`", prompt.name));
+            if let Some(ref description) = prompt.description {
+                output.push_str(&format!(": {}", description.trim()));
+            }
+            output.push('\n');
+            for arg in &prompt.arguments {
+                let required = if arg.required { " (required)" } else { "" };
+                output.push_str(&format!("      - `{}`{}", arg.name, required));
+                if let Some(ref description) = arg.description {
+                    output.push_str(&format!(": {}", description.trim()));
+                }
+                output.push('\n');
+            }
+        }
+
         Ok(output)
     }
 }
@@ -0,0 +1,146 @@
+use crate::model::{McpConfig, McpConflict, McpDefinition, McpDifference, McpServer};
+use std::collections::{BTreeMap, HashMap};
+
+/// Group definitions sharing a name within a config directory. Local
+/// servers win over project ones, which win over user ones; among user
+/// files settings.json comes first, then mcp.json, then ~/.claude.json.
+/// Plugin servers are namespaced by their plugin and never clash.
+pub fn find_conflicts(definitions: Vec<McpServer>) -> Vec<McpConflict> {
+    let mut groups: BTreeMap<(String, Option<String>), Vec<McpServer>> = BTreeMap::new();
+    for server in definitions.into_iter().filter(|s| s.plugin.is_none()) {
+        let config_dir = server.config_dir.as_ref().map(|d| d.display().to_string());
+        groups
+            .entry((server.name.clone(), config_dir))
+            .or_default()
+            .push(server);
+    }
+
+    groups
+        .into_values()
+        .filter(|servers| servers.len() > 1)
+        .map(|mut servers| {
+            // Stable, so definitions of one scope stay in file order
+            servers.sort_by_key(|s| std::cmp::Reverse(s.scope));
+            let name = servers[0].name.clone();
+            let winner = servers[0].config.clone();
+            let definitions = servers
+                .into_iter()
+                .enumerate()
+                .map(|(i, server)| {
+                    let differences = match (&winner, &server.config) {
+                        (Some(winner), Some(config)) if i > 0 => differences(config, winner),
+                        _ => Vec::new(),
+                    };
+                    McpDefinition {
+                        server,
+                        wins: i == 0,
+                        differences,
+                    }
+                })
+                .collect();
+            McpConflict { name, definitions }
+        })
+        .collect()
+}
+
+/// What sets `shadowed` apart from `winner`, compared on the raw values.
+/// Formatters mask secrets in what they show.
+fn differences(shadowed: &McpConfig, winner: &McpConfig) -> Vec<McpDifference> {
+    let mut differences = Vec::new();
+    if shadowed.transport.to_string() != winner.transport.to_string() {
+        differences.push(McpDifference::Transport {
+            shadowed: shadowed.transport.to_string(),
+            winner: winner.transport.to_string(),
+        });
+    }
+    if shadowed.transport.url() != winner.transport.url() {
+        differences.push(McpDifference::Url {
+            shadowed: shadowed.transport.url().map(str::to_string),
+            winner: winner.transport.url().map(str::to_string),
+        });
+    }
+    if shadowed.command != winner.command {
+        differences.push(McpDifference::Command {
+            shadowed: shadowed.command.clone(),
+            winner: winner.command.clone(),
+        });
+    }
+    if shadowed.args.as_deref().unwrap_or_default() != winner.args.as_deref().unwrap_or_default() {
+        differences.push(McpDifference::Args {
+            shadowed: shadowed.args.clone().unwrap_or_default(),
+            winner: winner.args.clone().unwrap_or_default(),
+        });
+    }
+    if let Some(keys) = differing_keys(shadowed.env.as_ref(), winner.env.as_ref()) {
+        differences.push(McpDifference::Env { keys });
+    }
+    let headers = differing_keys(shadowed.transport.headers(), winner.transport.headers());
+    if let Some(keys) = headers {
+        differences.push(McpDifference::Headers { keys });
+    }
+    differences
+}
+
+/// Keys set in only one map or to different values, sorted
+fn differing_keys(
+    a: Option<&HashMap<String, String>>,
+    b: Option<&HashMap<String, String>>,
+) -> Option<Vec<String>> {
+    let empty = HashMap::new();
+    let (a, b) = (a.unwrap_or(&empty), b.unwrap_or(&empty));
+    let mut keys: Vec<String> = a
+        .keys()
+        .chain(b.keys())
+        .filter(|key| a.get(*key) != b.get(*key))
+        .cloned()
+        .collect();
+    keys.sort_unstable();
+    keys.dedup();
+    (!keys.is_empty()).then_some(keys)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{McpTransport, Scope};
+
+    fn server(scope: Scope, args: &[&str]) -> McpServer {
+        McpServer::new("db")
+            .with_scope(scope)
+            .with_config(McpConfig {
+                transport: McpTransport::Stdio,
+                command: Some("db-server".to_string()),
+                args: Some(args.iter().map(|a| a.to_string()).collect()),
+                env: None,
+            })
+    }
+
+    #[test]
+    fn secrets_that_mask_alike_still_differ() {
+        let conflicts = find_conflicts(vec![
+            server(Scope::User, &["--api-key", "hunter2pass"]),
+            server(Scope::Project, &["--api-key", "hunter3pass"]),
+        ]);
+
+        let definitions = &conflicts[0].definitions;
+        assert!(definitions[0].wins);
+        assert_eq!(definitions[0].server.scope, Scope::Project);
+        assert_eq!(
+            definitions[1].differences,
+            [McpDifference::Args {
+                shadowed: vec!["--api-key".to_string(), "hunter2pass".to_string()],
+                winner: vec!["--api-key".to_string(), "hunter3pass".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn identical_definitions_have_no_differences() {
+        let conflicts = find_conflicts(vec![
+            server(Scope::User, &["--port", "5432"]),
+            server(Scope::Local, &["--port", "5432"]),
+        ]);
+
+        assert!(conflicts[0].definitions[1].differences.is_empty());
+    }
+}
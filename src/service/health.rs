@@ -0,0 +1,122 @@
+use crate::client::{InitializeResult, StdioClient};
+use crate::error::McpClientError;
+use crate::model::{
+    ConnectionStatus, McpCapabilities, McpConfig, McpDetail, McpHealth, McpServer, McpTransport,
+};
+use serde::de::DeserializeOwned;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Check every server concurrently, updating `status` and `health` in place
+pub fn check_servers(mcps: &mut [McpServer], timeout: Duration) {
+    thread::scope(|scope| {
+        for mcp in mcps.iter_mut() {
+            scope.spawn(move || check_server(mcp, timeout));
+        }
+    });
+}
+
+/// Spawn a stdio server and perform the `initialize` handshake. Remote
+/// transports, and servers Claude Code would not start because they are
+/// disabled or unapproved, are left as `Unknown`.
+pub fn check_server(mcp: &mut McpServer, timeout: Duration) {
+    if mcp.disabled || mcp.unapproved {
+        return;
+    }
+    let config = match mcp.config {
+        Some(ref config) if config.transport == McpTransport::Stdio => config,
+        _ => return,
+    };
+
+    match connect(config, timeout) {
+        Ok((_, health, _)) => {
+            mcp.status = ConnectionStatus::Connected;
+            mcp.health = Some(health);
+        }
+        Err(e) => mcp.status = status_for(e),
+    }
+}
+
+/// Like `check_server`, then list the tools, resources and prompts the
+/// server advertises in its capabilities.
+pub fn inspect_server(detail: &mut McpDetail, timeout: Duration) {
+    if detail.disabled || detail.unapproved || detail.config.transport != McpTransport::Stdio {
+        return;
+    }
+
+    let (mut client, health, init) = match connect(&detail.config, timeout) {
+        Ok(connected) => connected,
+        Err(e) => {
+            detail.status = status_for(e);
+            return;
+        }
+    };
+    detail.status = ConnectionStatus::Connected;
+    detail.health = Some(health);
+
+    match list_capabilities(&mut client, &init) {
+        Ok(capabilities) => detail.capabilities = Some(capabilities),
+        Err(e) => detail.status = ConnectionStatus::Error(e.to_string()),
+    }
+}
+
+fn connect(
+    config: &McpConfig,
+    timeout: Duration,
+) -> Result<(StdioClient, McpHealth, InitializeResult), McpClientError> {
+    let started = Instant::now();
+    let mut client = StdioClient::spawn(config, timeout)?;
+    let init = client.initialize()?;
+
+    let server_info = init.server_info.clone().unwrap_or_default();
+    let health = McpHealth {
+        server_name: server_info.name,
+        server_version: server_info.version,
+        protocol_version: init.protocol_version.clone(),
+        latency_ms: started.elapsed().as_millis() as u64,
+    };
+    Ok((client, health, init))
+}
+
+fn list_capabilities(
+    client: &mut StdioClient,
+    init: &InitializeResult,
+) -> Result<McpCapabilities, McpClientError> {
+    let mut capabilities = McpCapabilities::default();
+    // Only call the list methods for capabilities the server declared
+    let declares = |name: &str| init.capabilities.get(name).is_some();
+
+    if declares("tools") {
+        capabilities.tools = list_typed(client, "tools/list", "tools")?;
+    }
+    if declares("resources") {
+        capabilities.resources = list_typed(client, "resources/list", "resources")?;
+    }
+    if declares("prompts") {
+        capabilities.prompts = list_typed(client, "prompts/list", "prompts")?;
+    }
+
+    Ok(capabilities)
+}
+
+fn list_typed<T: DeserializeOwned>(
+    client: &mut StdioClient,
+    method: &str,
+    key: &str,
+) -> Result<Vec<T>, McpClientError> {
+    client
+        .list_all(method, key)?
+        .into_iter()
+        .map(|item| {
+            serde_json::from_value(item)
+                .map_err(|e| McpClientError::Protocol(format!("{}: {}", method, e)))
+        })
+        .collect()
+}
+
+fn status_for(error: McpClientError) -> ConnectionStatus {
+    match error {
+        McpClientError::Closed => ConnectionStatus::Disconnected,
+        e => ConnectionStatus::Error(e.to_string()),
+    }
+}
@@ -0,0 +1,256 @@
+use crate::error::CliError;
+use crate::model::Severity;
+use crate::service::lint::lint_skill;
+use crate::source::frontmatter::parse_skill_md_full;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Entries never copied out of a source
+const SKIPPED: [&str; 3] = [".git", "__MACOSX", ".DS_Store"];
+
+/// A skill unpacked and ready to copy: a directory, an extracted zip
+/// archive or a cloned git repository
+pub struct StagedSkill {
+    pub name: String,
+    /// Directory holding SKILL.md
+    pub dir: PathBuf,
+    /// Temporary directory removed on drop, for archives and clones
+    scratch: Option<PathBuf>,
+}
+
+impl Drop for StagedSkill {
+    fn drop(&mut self) {
+        if let Some(ref scratch) = self.scratch {
+            let _ = fs::remove_dir_all(scratch);
+        }
+    }
+}
+
+/// Unpack `source` and check that it is a skill `lint` accepts
+pub fn stage(source: &str) -> Result<StagedSkill, CliError> {
+    let path = Path::new(source);
+    // URLs and `*.git` paths, bare repositories included, are cloned
+    let (root, scratch) = if is_git_url(source) {
+        let scratch = scratch_dir()?;
+        clone(source, &scratch).map_err(|e| cleanup(&scratch, e))?;
+        (scratch.clone(), Some(scratch))
+    } else if path.is_dir() {
+        (path.to_path_buf(), None)
+    } else if path.is_file() {
+        let scratch = scratch_dir()?;
+        extract_zip(path, &scratch).map_err(|e| cleanup(&scratch, e))?;
+        (scratch.clone(), Some(scratch))
+    } else {
+        return Err(
+            io::Error::new(io::ErrorKind::NotFound, format!("{}: not found", source)).into(),
+        );
+    };
+
+    let mut staged = StagedSkill {
+        name: String::new(),
+        dir: root.clone(),
+        scratch,
+    };
+    staged.dir = find_skill_dir(&root).ok_or_else(|| {
+        CliError::InvalidSkill(root.clone(), vec!["SKILL.md not found".to_string()])
+    })?;
+
+    let content = fs::read_to_string(staged.dir.join("SKILL.md"))?;
+    let name = parse_skill_md_full(&content)
+        .ok()
+        .and_then(|(start_matter, _)| start_matter.name)
+        .unwrap_or_default();
+
+    // The copy is named after the frontmatter, so the source directory's
+    // name does not have to match
+    let problems: Vec<String> = lint_skill(&name, &staged.dir)
+        .into_iter()
+        .filter(|f| f.severity == Severity::Error && f.rule != "name-mismatch")
+        .map(|f| match f.line {
+            Some(line) => format!("SKILL.md:{}: {}", line, f.message),
+            None => f.message,
+        })
+        .collect();
+    if !problems.is_empty() {
+        return Err(CliError::InvalidSkill(PathBuf::from(source), problems));
+    }
+
+    staged.name = name;
+    Ok(staged)
+}
+
+/// The directory with SKILL.md: the root itself, or its only subdirectory,
+/// as in archives that wrap the skill in a folder
+fn find_skill_dir(root: &Path) -> Option<PathBuf> {
+    if root.join("SKILL.md").is_file() {
+        return Some(root.to_path_buf());
+    }
+
+    let subdirs: Vec<PathBuf> = fs::read_dir(root)
+        .ok()?
+        .flatten()
+        .filter(|e| !SKIPPED.contains(&e.file_name().to_string_lossy().as_ref()))
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .collect();
+    match subdirs.as_slice() {
+        [only] if only.join("SKILL.md").is_file() => Some(only.clone()),
+        _ => None,
+    }
+}
+
+/// Files under `dir` relative to it, in the order they would be copied.
+/// Symlinks are left out: they could point anywhere, `~/.ssh` included.
+pub fn files(dir: &Path) -> Result<Vec<PathBuf>, CliError> {
+    let mut files = Vec::new();
+    let mut pending = vec![PathBuf::new()];
+    while let Some(relative) = pending.pop() {
+        for entry in fs::read_dir(dir.join(&relative))? {
+            let entry = entry?;
+            if SKIPPED.contains(&entry.file_name().to_string_lossy().as_ref()) {
+                continue;
+            }
+            let path = relative.join(entry.file_name());
+            let file_type = entry.file_type()?;
+            if file_type.is_symlink() {
+                continue;
+            }
+            if file_type.is_dir() {
+                pending.push(path);
+            } else {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Copy the staged skill to `dest`, replacing whatever is there. The copy is
+/// written next to `dest` first so a failure leaves the old skill in place.
+pub fn copy_to(staged: &StagedSkill, dest: &Path) -> Result<Vec<PathBuf>, CliError> {
+    let parent = dest.parent().unwrap_or(dest);
+    fs::create_dir_all(parent)?;
+    let temp = parent.join(format!(".{}.installing", staged.name));
+    if temp.exists() {
+        fs::remove_dir_all(&temp)?;
+    }
+
+    let files = files(&staged.dir)?;
+    let copied = files.iter().try_for_each(|file| {
+        let target = temp.join(file);
+        if let Some(dir) = target.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::copy(staged.dir.join(file), target).map(|_| ())
+    });
+    if let Err(e) = copied {
+        return Err(cleanup(&temp, e.into()));
+    }
+
+    if dest.exists() {
+        fs::remove_dir_all(dest)?;
+    }
+    fs::rename(&temp, dest)?;
+    Ok(files)
+}
+
+fn is_git_url(source: &str) -> bool {
+    source.starts_with("https://")
+        || source.starts_with("http://")
+        || source.starts_with("ssh://")
+        || source.starts_with("git@")
+        || source.ends_with(".git")
+}
+
+fn clone(url: &str, dest: &Path) -> Result<(), CliError> {
+    let output = Command::new("git")
+        // `--` keeps a URL such as `--upload-pack=...` from being an option
+        .args(["clone", "--depth", "1", "--quiet", "--", url])
+        .arg(dest)
+        .output()?;
+    if !output.status.success() {
+        let message = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(io::Error::other(format!("git clone failed: {}", message)).into());
+    }
+    Ok(())
+}
+
+fn extract_zip(archive: &Path, dest: &Path) -> Result<(), CliError> {
+    let mut zip = zip::ZipArchive::new(fs::File::open(archive)?)?;
+    for i in 0..zip.len() {
+        let mut entry = zip.by_index(i)?;
+        // Entries escaping the destination with `..` or absolute paths are skipped
+        let relative = match entry.enclosed_name() {
+            Some(name) => name.to_path_buf(),
+            None => continue,
+        };
+        let target = dest.join(relative);
+        if entry.is_dir() {
+            fs::create_dir_all(&target)?;
+            continue;
+        }
+        if let Some(dir) = target.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = fs::File::create(&target)?;
+        io::copy(&mut entry, &mut file)?;
+
+        // Permission bits only; setuid, setgid and sticky bits are dropped
+        #[cfg(unix)]
+        if let Some(mode) = entry.unix_mode() {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&target, fs::Permissions::from_mode(mode & 0o777))?;
+        }
+    }
+    Ok(())
+}
+
+fn scratch_dir() -> Result<PathBuf, CliError> {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    let dir = std::env::temp_dir().join(format!(
+        "claude-list-install-{}-{}",
+        std::process::id(),
+        nanos
+    ));
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Remove a half-written directory, passing the error through
+fn cleanup(dir: &Path, e: CliError) -> CliError {
+    let _ = fs::remove_dir_all(dir);
+    e
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[cfg(unix)]
+    #[test]
+    fn files_leave_out_symlinks() {
+        let outside = TempDir::new();
+        outside.write("id_rsa", "secret");
+        let skill = TempDir::new();
+        skill.write("SKILL.md", "---\nname: pdf\n---\n");
+        skill.write("scripts/run.sh", "echo");
+        std::os::unix::fs::symlink(outside.path().join("id_rsa"), skill.path().join("key"))
+            .unwrap();
+        std::os::unix::fs::symlink(outside.path(), skill.path().join("linked")).unwrap();
+
+        let mut found = files(skill.path()).unwrap();
+        found.sort();
+        assert_eq!(
+            found,
+            [PathBuf::from("SKILL.md"), PathBuf::from("scripts/run.sh")]
+        );
+    }
+}
@@ -0,0 +1,434 @@
+use crate::model::{LintFinding, Severity};
+use crate::source::frontmatter::{parse_skill_md_full, split_frontmatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const NAME_MAX_LEN: usize = 64;
+pub const DESCRIPTION_MAX_LEN: usize = 1024;
+/// Words a skill name may not contain
+pub const RESERVED_WORDS: [&str; 2] = ["anthropic", "claude"];
+/// SKILL.md bodies past this many lines should be split into referenced files
+pub const BODY_MAX_LINES: usize = 500;
+
+/// Findings for one skill directory
+struct Lint<'a> {
+    skill: &'a str,
+    findings: Vec<LintFinding>,
+}
+
+impl Lint<'_> {
+    fn push(
+        &mut self,
+        severity: Severity,
+        rule: &str,
+        file: &Path,
+        line: Option<usize>,
+        message: String,
+    ) {
+        self.findings.push(LintFinding {
+            skill: self.skill.to_string(),
+            severity,
+            rule: rule.to_string(),
+            file: file.to_path_buf(),
+            line,
+            message,
+        });
+    }
+}
+
+/// Check a skill directory against the SKILL.md spec
+pub fn lint_skill(skill: &str, dir: &Path) -> Vec<LintFinding> {
+    let mut lint = Lint {
+        skill,
+        findings: Vec::new(),
+    };
+    let skill_md = dir.join("SKILL.md");
+
+    // Compare names exactly: a case-insensitive file system would find
+    // `skill.md` when asked for `SKILL.md`
+    let files: Vec<String> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    for file in files
+        .iter()
+        .filter(|f| f.eq_ignore_ascii_case("SKILL.md") && *f != "SKILL.md")
+    {
+        lint.push(
+            Severity::Error,
+            "skill-md-case",
+            &dir.join(file),
+            None,
+            format!("'{}' is ignored; rename it to SKILL.md", file),
+        );
+    }
+    if !files.iter().any(|f| f == "SKILL.md") {
+        lint.push(
+            Severity::Error,
+            "missing-skill-md",
+            &skill_md,
+            None,
+            "SKILL.md not found".to_string(),
+        );
+        return lint.findings;
+    }
+
+    let content = match fs::read_to_string(&skill_md) {
+        Ok(content) => content,
+        Err(e) => {
+            lint.push(
+                Severity::Error,
+                "unreadable",
+                &skill_md,
+                None,
+                e.to_string(),
+            );
+            return lint.findings;
+        }
+    };
+
+    let yaml = match split_frontmatter(&content) {
+        Ok(Some((yaml, _))) => yaml,
+        Ok(None) => {
+            lint.push(
+                Severity::Error,
+                "missing-frontmatter",
+                &skill_md,
+                Some(1),
+                "SKILL.md must start with '---' YAML frontmatter".to_string(),
+            );
+            return lint.findings;
+        }
+        Err(e) => {
+            lint.push(
+                Severity::Error,
+                "invalid-frontmatter",
+                &skill_md,
+                e.line,
+                e.message,
+            );
+            return lint.findings;
+        }
+    };
+    let (start_matter, _) = match parse_skill_md_full(&content) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            lint.push(
+                Severity::Error,
+                "invalid-frontmatter",
+                &skill_md,
+                e.line,
+                e.message,
+            );
+            return lint.findings;
+        }
+    };
+
+    let dir_name = dir
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let name_line = Some(key_line(yaml, "name"));
+    match start_matter.name.as_deref().filter(|n| !n.is_empty()) {
+        None => lint.push(
+            Severity::Error,
+            "name-missing",
+            &skill_md,
+            name_line,
+            "frontmatter has no 'name'".to_string(),
+        ),
+        Some(name) => {
+            if name != dir_name {
+                lint.push(
+                    Severity::Error,
+                    "name-mismatch",
+                    &skill_md,
+                    name_line,
+                    format!("name '{}' does not match directory '{}'", name, dir_name),
+                );
+            }
+            for (rule, message) in name_problems(name) {
+                lint.push(Severity::Error, rule, &skill_md, name_line, message);
+            }
+        }
+    }
+
+    let description_line = Some(key_line(yaml, "description"));
+    match start_matter
+        .description
+        .as_deref()
+        .filter(|d| !d.is_empty())
+    {
+        None => lint.push(
+            Severity::Error,
+            "description-missing",
+            &skill_md,
+            description_line,
+            "frontmatter has no 'description'".to_string(),
+        ),
+        Some(description) if description.chars().count() > DESCRIPTION_MAX_LEN => lint.push(
+            Severity::Error,
+            "description-length",
+            &skill_md,
+            description_line,
+            format!(
+                "description is longer than {} characters",
+                DESCRIPTION_MAX_LEN
+            ),
+        ),
+        Some(_) => {}
+    }
+
+    // The opening and closing `---` plus the YAML between them
+    let body_start = yaml.lines().count() + 2;
+    let body_lines = content.lines().count().saturating_sub(body_start);
+    if body_lines > BODY_MAX_LINES {
+        lint.push(
+            Severity::Warning,
+            "body-length",
+            &skill_md,
+            None,
+            format!(
+                "body is {} lines; keep it under {} and move details into referenced files",
+                body_lines, BODY_MAX_LINES
+            ),
+        );
+    }
+
+    let body_offset: usize = content
+        .split_inclusive('\n')
+        .take(body_start)
+        .map(str::len)
+        .sum();
+    for (i, target) in references(&content[body_offset..]) {
+        if !dir.join(&target).exists() {
+            lint.push(
+                Severity::Error,
+                "missing-reference",
+                &skill_md,
+                Some(body_start + i + 1),
+                format!("referenced file '{}' does not exist", target.display()),
+            );
+        }
+    }
+
+    lint.findings
+}
+
+/// Why a skill name breaks the spec, as `(rule, message)` pairs
+pub fn name_problems(name: &str) -> Vec<(&'static str, String)> {
+    let mut problems = Vec::new();
+    if !is_valid_name(name) {
+        problems.push((
+            "name-format",
+            format!(
+                "name '{}' must be lowercase letters, digits and single hyphens",
+                name
+            ),
+        ));
+    }
+    if name.chars().count() > NAME_MAX_LEN {
+        problems.push((
+            "name-length",
+            format!("name is longer than {} characters", NAME_MAX_LEN),
+        ));
+    }
+    let lower = name.to_lowercase();
+    for word in RESERVED_WORDS.iter().filter(|w| lower.contains(*w)) {
+        problems.push((
+            "reserved-word",
+            format!("name contains the reserved word '{}'", word),
+        ));
+    }
+    problems
+}
+
+/// Lowercase letters and digits, separated by single hyphens
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.split('-').all(|part| {
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        })
+}
+
+/// Line in the file of a top-level frontmatter key, or the opening `---`
+fn key_line(yaml: &str, key: &str) -> usize {
+    yaml.lines()
+        .position(|line| {
+            line.strip_prefix(key)
+                .is_some_and(|rest| rest.trim_start().starts_with(':'))
+        })
+        .map(|i| i + 2)
+        .unwrap_or(1)
+}
+
+/// Local files linked with `[text](path)` outside code blocks, with the
+/// 0-based line they are on
+pub(crate) fn references(markdown: &str) -> Vec<(usize, PathBuf)> {
+    let mut in_code = false;
+    let mut targets = Vec::new();
+    for (i, line) in markdown.lines().enumerate() {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+        } else if !in_code {
+            targets.extend(link_targets(line).into_iter().map(|t| (i, t)));
+        }
+    }
+    targets
+}
+
+/// Local files a line links to with `[text](path)`
+fn link_targets(line: &str) -> Vec<PathBuf> {
+    let mut targets = Vec::new();
+    let mut rest = line;
+    while let Some(start) = rest.find("](") {
+        rest = &rest[start + 2..];
+        let end = match rest.find(')') {
+            Some(end) => end,
+            None => break,
+        };
+        // Drop a title (`path "title"`) and an anchor
+        let target = rest[..end].split_whitespace().next().unwrap_or("");
+        let target = target.split('#').next().unwrap_or("");
+        let target = target.trim_start_matches('<').trim_end_matches('>');
+        if !target.is_empty() && !target.contains("://") && !target.starts_with("mailto:") {
+            targets.push(PathBuf::from(target));
+        }
+        rest = &rest[end..];
+    }
+    targets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    /// Rule and line of each finding for `skills/<name>/SKILL.md` holding `content`
+    fn lint(name: &str, content: &str) -> Vec<(String, Option<usize>)> {
+        let dir = TempDir::new();
+        dir.write(&format!("skills/{}/SKILL.md", name), content);
+        lint_skill(name, &dir.path().join("skills").join(name))
+            .into_iter()
+            .map(|f| (f.rule, f.line))
+            .collect()
+    }
+
+    fn rules(findings: &[(String, Option<usize>)]) -> Vec<&str> {
+        findings.iter().map(|(rule, _)| rule.as_str()).collect()
+    }
+
+    #[test]
+    fn a_valid_skill_has_no_findings() {
+        let findings = lint(
+            "pdf",
+            "---\nname: pdf\ndescription: Fill PDF forms\n---\n# PDF\n",
+        );
+        assert!(findings.is_empty(), "{:?}", findings);
+    }
+
+    #[test]
+    fn a_lowercase_skill_md_is_reported() {
+        let dir = TempDir::new();
+        dir.write("pdf/skill.md", "---\nname: pdf\ndescription: x\n---\n");
+
+        let findings: Vec<String> = lint_skill("pdf", &dir.path().join("pdf"))
+            .into_iter()
+            .map(|f| f.rule)
+            .collect();
+        assert_eq!(findings, ["skill-md-case", "missing-skill-md"]);
+    }
+
+    #[test]
+    fn frontmatter_must_be_present_and_closed() {
+        assert_eq!(
+            lint("pdf", "# PDF\n"),
+            [("missing-frontmatter".to_string(), Some(1))]
+        );
+        assert_eq!(
+            rules(&lint("pdf", "---\nname: pdf\n")),
+            ["invalid-frontmatter"]
+        );
+    }
+
+    #[test]
+    fn name_problems_point_at_the_name_line() {
+        let findings = lint(
+            "pdf",
+            "---\ndescription: Fill forms\nname: Claude_PDF\n---\n",
+        );
+        assert_eq!(
+            findings,
+            [
+                ("name-mismatch".to_string(), Some(3)),
+                ("name-format".to_string(), Some(3)),
+                ("reserved-word".to_string(), Some(3)),
+            ]
+        );
+    }
+
+    #[test]
+    fn names_are_lowercase_hyphenated_and_short() {
+        assert!(name_problems("pdf-forms-2").is_empty());
+        for name in ["-pdf", "pdf--forms", "pdf-", "PDF", "pdf_forms", ""] {
+            let problems = name_problems(name);
+            assert_eq!(problems[0].0, "name-format", "{}", name);
+        }
+        let long = "a".repeat(NAME_MAX_LEN + 1);
+        assert_eq!(name_problems(&long)[0].0, "name-length");
+    }
+
+    #[test]
+    fn descriptions_must_be_present_and_bounded() {
+        assert_eq!(
+            lint("pdf", "---\nname: pdf\n---\n"),
+            [("description-missing".to_string(), Some(1))]
+        );
+        let long = "x".repeat(DESCRIPTION_MAX_LEN + 1);
+        let content = format!("---\nname: pdf\ndescription: {}\n---\n", long);
+        assert_eq!(
+            lint("pdf", &content),
+            [("description-length".to_string(), Some(3))]
+        );
+    }
+
+    #[test]
+    fn missing_references_are_reported_on_their_line() {
+        let dir = TempDir::new();
+        dir.write("pdf/reference.md", "Forms");
+        dir.write(
+            "pdf/SKILL.md",
+            "---\nname: pdf\ndescription: x\n---\n\nSee [forms](reference.md#fields)\n\
+             and [tables](tables.md \"Tables\") or [site](https://example.com).\n\
+             ```\n[not a link](code.md)\n```\n",
+        );
+
+        let findings = lint_skill("pdf", &dir.path().join("pdf"));
+        assert_eq!(findings.len(), 1, "{:?}", findings);
+        assert_eq!(findings[0].rule, "missing-reference");
+        assert_eq!(findings[0].line, Some(7));
+        assert!(findings[0].message.contains("tables.md"));
+    }
+
+    #[test]
+    fn long_bodies_are_a_warning() {
+        let content = format!(
+            "---\nname: pdf\ndescription: x\n---\n{}",
+            "line\n".repeat(BODY_MAX_LINES + 1)
+        );
+        let dir = TempDir::new();
+        dir.write("pdf/SKILL.md", &content);
+
+        let findings = lint_skill("pdf", &dir.path().join("pdf"));
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].rule, "body-length");
+        assert_eq!(findings[0].severity, Severity::Warning);
+    }
+}
@@ -0,0 +1,163 @@
+use crate::model::{ApiInfo, LocalInfo, Skill, SourceType};
+
+/// Merge API skills into local skills with the same name.
+///
+/// A local skill that is also uploaded becomes `SourceType::Both`, with the
+/// local path and version in `local` and the API id and version in `api`.
+/// When several local skills share the name, the last one (the
+/// highest-precedence scope in a sorted list) is merged. API skills without
+/// a local counterpart are kept as they are.
+pub fn merge_skills(skills: Vec<Skill>) -> Vec<Skill> {
+    let (api, mut merged): (Vec<Skill>, Vec<Skill>) = skills
+        .into_iter()
+        .partition(|s| s.source == SourceType::Api);
+
+    for api_skill in api {
+        let matched = merged
+            .iter_mut()
+            .rev()
+            .find(|s| s.source == SourceType::Local && s.name == api_skill.name);
+
+        match matched {
+            Some(skill) => merge_into(skill, api_skill),
+            None => merged.push(api_skill),
+        }
+    }
+
+    merged.sort_by(|a, b| a.name.cmp(&b.name));
+    merged
+}
+
+fn merge_into(skill: &mut Skill, api_skill: Skill) {
+    skill.drift = has_drifted(skill, &api_skill);
+    skill.source = SourceType::Both;
+    skill.local = Some(LocalInfo {
+        path: skill.path.clone(),
+        version: skill.version.clone(),
+    });
+    skill.api = Some(ApiInfo {
+        id: api_skill.id.clone(),
+        version: api_skill.version,
+    });
+    skill.id = api_skill.id;
+}
+
+/// The uploaded copy is stale when its version differs from the local one.
+/// The Skills API numbers uploads with an id of digits rather than the
+/// version the skill declares; without a declared version on both sides,
+/// the descriptions are compared instead.
+fn has_drifted(local: &Skill, api: &Skill) -> bool {
+    let api_version = declared(api).filter(|v| !v.chars().all(|c| c.is_ascii_digit()));
+    if let (Some(local), Some(api)) = (declared(local), api_version) {
+        return local != api;
+    }
+
+    match (&local.description, &api.description) {
+        (Some(local), Some(api)) => local.trim() != api.trim(),
+        (None, None) => false,
+        _ => true,
+    }
+}
+
+/// A skill's version without a leading `v`, if it has one
+fn declared(skill: &Skill) -> Option<&str> {
+    skill
+        .version
+        .as_deref()
+        .map(|v| v.trim().trim_start_matches('v'))
+        .filter(|v| !v.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn local(name: &str, version: &str, description: &str) -> Skill {
+        let mut skill = Skill::new(name).with_version(version);
+        skill.description = Some(description.to_string());
+        skill
+    }
+
+    fn api(name: &str, description: &str) -> Skill {
+        api_versioned(name, "1759178010641129", description)
+    }
+
+    fn api_versioned(name: &str, version: &str, description: &str) -> Skill {
+        let mut skill = Skill::new(name)
+            .with_source(SourceType::Api)
+            .with_id("skill_01")
+            .with_version(version);
+        skill.description = Some(description.to_string());
+        skill
+    }
+
+    #[test]
+    fn merged_skills_keep_both_halves() {
+        let merged = merge_skills(vec![
+            local("pdf", "1.2.0", "Fill PDF forms"),
+            api("pdf", "Fill PDF forms"),
+        ]);
+
+        assert_eq!(merged.len(), 1);
+        let skill = &merged[0];
+        assert_eq!(skill.source, SourceType::Both);
+        assert_eq!(
+            skill.local.as_ref().unwrap().version.as_deref(),
+            Some("1.2.0")
+        );
+        let uploaded = skill.api.as_ref().unwrap();
+        assert_eq!(uploaded.id.as_deref(), Some("skill_01"));
+        assert_eq!(uploaded.version.as_deref(), Some("1759178010641129"));
+        // A versioned local skill matching its upload has not drifted
+        assert!(!skill.drift);
+
+        let json = serde_json::to_value(skill).unwrap();
+        assert_eq!(json["source"], "both");
+    }
+
+    #[test]
+    fn a_changed_description_is_drift() {
+        let merged = merge_skills(vec![
+            local("pdf", "1.2.0", "Fill and sign PDF forms"),
+            api("pdf", "Fill PDF forms"),
+        ]);
+
+        assert!(merged[0].drift);
+    }
+
+    #[test]
+    fn declared_versions_decide_drift_when_both_sides_have_one() {
+        let stale = merge_skills(vec![
+            local("pdf", "1.3.0", "Fill PDF forms"),
+            api_versioned("pdf", "1.2.0", "Fill PDF forms"),
+        ]);
+        assert!(stale[0].drift);
+
+        let current = merge_skills(vec![
+            local("pdf", "v1.3.0", "Fill and sign PDF forms"),
+            api_versioned("pdf", "1.3.0", "Fill PDF forms"),
+        ]);
+        assert!(!current[0].drift);
+    }
+
+    #[test]
+    fn upload_ids_fall_back_to_descriptions() {
+        let merged = merge_skills(vec![
+            local("pdf", "1.2.0", "Fill PDF forms"),
+            api_versioned("pdf", "1759178010641129", "Fill PDF forms"),
+        ]);
+        assert!(!merged[0].drift);
+    }
+
+    #[test]
+    fn unmatched_skills_are_kept_apart() {
+        let merged = merge_skills(vec![
+            local("docx", "1.0.0", "Edit documents"),
+            api("pdf", "Fill PDF forms"),
+        ]);
+
+        let sources: Vec<&SourceType> = merged.iter().map(|s| &s.source).collect();
+        assert_eq!(sources, [&SourceType::Local, &SourceType::Api]);
+        assert!(merged.iter().all(|s| s.local.is_none() && s.api.is_none()));
+    }
+}
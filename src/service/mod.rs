@@ -1,56 +1,986 @@
-use crate::error::CliError;
-use crate::model::{ClaudeList, DetailItem, McpServer, Skill};
-use crate::source::{LocalSource, McpSource, SkillSource};
+use crate::error::{CliError, LocalSourceError};
+use crate::model::{
+    Agent, AuditReport, ClaudeList, Command, DetailItem, Hook, LintReport, McpChange, McpConflict,
+    McpDetail, McpServer, Plugin, Scope, Skill, SkillChange, Warning,
+};
+use crate::source::json_file::SavedFile;
+use crate::source::local::managed_settings_path;
+use crate::source::registry::RegisteredSource;
+use crate::source::{
+    find_by_name, ApiSource, LocalLayer, LocalSource, McpServerSpec, McpSource, Source,
+    SourceRegistry,
+};
+use std::cell::{OnceCell, RefCell};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
-pub struct ListService;
+pub mod audit;
+pub mod conflicts;
+pub mod health;
+pub mod install;
+pub mod lint;
+pub mod merger;
+pub mod resolve;
+pub mod scaffold;
+pub mod tokens;
+
+pub use scaffold::NewSkill;
+
+/// Name of the Skills API source
+pub const API_SOURCE: &str = "api";
+
+/// The local sources, one per layer of local configuration, with their priorities
+pub const LOCAL_SOURCES: [(&str, i32, LocalLayer); 3] = [
+    ("plugin", 10, LocalLayer::Plugin),
+    ("user", 20, LocalLayer::User),
+    ("project", 30, LocalLayer::Project),
+];
+
+pub struct ListService {
+    all_projects: bool,
+    timeout: Duration,
+    /// Config directories to read instead of the default one
+    config_dirs: Vec<PathBuf>,
+    /// Names passed to `--source`; empty means every available source
+    selected: Vec<String>,
+    /// Whether to register the built-in local and API sources
+    defaults: bool,
+    extra: RefCell<SourceRegistry>,
+    registry: OnceCell<SourceRegistry>,
+    warnings: RefCell<Vec<Warning>>,
+}
 
 impl ListService {
     pub fn new() -> Self {
-        Self
+        Self {
+            all_projects: false,
+            timeout: Duration::from_secs(10),
+            config_dirs: Vec::new(),
+            selected: Vec::new(),
+            defaults: true,
+            extra: RefCell::default(),
+            registry: OnceCell::new(),
+            warnings: RefCell::default(),
+        }
     }
 
-    pub fn list_all(&self) -> Result<ClaudeList, CliError> {
-        let local = LocalSource::new()?;
-        let skills = local.list_skills()?;
-        let mcps = local.list_mcps()?;
+    /// Only read from the named sources. Naming a source makes it required:
+    /// its failures become errors instead of warnings.
+    pub fn with_sources(mut self, names: Vec<String>) -> Self {
+        self.selected = names;
+        self
+    }
+
+    /// Register an additional source next to the built-in ones
+    pub fn with_source(
+        mut self,
+        name: impl Into<String>,
+        priority: i32,
+        source: impl Source + 'static,
+    ) -> Self {
+        self.extra.get_mut().register(name, priority, source);
+        self
+    }
+
+    /// Read only from `registry`, without the built-in sources
+    pub fn with_registry(mut self, registry: SourceRegistry) -> Self {
+        self.defaults = false;
+        *self.extra.get_mut() = registry;
+        self
+    }
+
+    /// How long to wait for an MCP server to answer during a health check
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Read these config directories instead of `$CLAUDE_CONFIG_DIR` or
+    /// `~/.claude`. With several, user and plugin items are listed from each
+    /// and labelled with their directory; project files are read once.
+    pub fn with_config_dirs(mut self, dirs: Vec<PathBuf>) -> Self {
+        self.config_dirs = dirs;
+        self
+    }
+
+    /// Show per-project MCP servers for every project, not only the current one
+    pub fn with_all_projects(mut self, all_projects: bool) -> Self {
+        self.all_projects = all_projects;
+        self
+    }
+
+    /// The registered sources, built on first use
+    fn sources(&self) -> Result<&SourceRegistry, CliError> {
+        if let Some(registry) = self.registry.get() {
+            return Ok(registry);
+        }
+
+        let mut registry = if self.defaults {
+            self.default_sources()?
+        } else {
+            SourceRegistry::new()
+        };
+        registry.extend(self.extra.take());
+        if !self.selected.is_empty() {
+            registry.select(&self.selected)?;
+        }
+        Ok(self.registry.get_or_init(|| registry))
+    }
+
+    /// The local layers, plus the API when `CLAUDE_API_KEY` is set.
+    /// A source that cannot be set up is skipped, with a warning when every
+    /// source was wanted and an error when it was selected by name.
+    fn default_sources(&self) -> Result<SourceRegistry, CliError> {
+        let selected = |name: &str| self.selected.iter().any(|s| s == name);
+        let mut registry = SourceRegistry::new();
+
+        for (name, priority, layer, local) in self.local_layers() {
+            match local {
+                Ok(local) => registry.register(
+                    name,
+                    priority,
+                    local
+                        .with_all_projects(self.all_projects)
+                        .with_layers(&[layer]),
+                ),
+                Err(e) if selected(&name) => return Err(e),
+                Err(e) if self.selected.is_empty() => self.warn(Warning::new(name, e.to_string())),
+                Err(_) => {}
+            }
+        }
+
+        match ApiSource::from_env() {
+            Ok(api) => registry.register(API_SOURCE, 0, api),
+            // Without a key the API is silently left out unless requested
+            Err(e) if selected(API_SOURCE) => return Err(e),
+            Err(_) => {}
+        }
 
-        Ok(ClaudeList { skills, mcps })
+        Ok(registry)
+    }
+
+    /// One local source per layer. With several config directories the user
+    /// and plugin layers are repeated per directory, named e.g. `user:<dir>`.
+    fn local_layers(&self) -> Vec<(String, i32, LocalLayer, Result<LocalSource, CliError>)> {
+        let cwd = std::env::current_dir().ok();
+        let open = |dir: &PathBuf| {
+            let local =
+                LocalSource::with_path(dir.clone()).with_managed_settings(managed_settings_path());
+            match cwd {
+                Some(ref cwd) => local.with_current_dir(cwd.clone()),
+                None => local,
+            }
+        };
+
+        let mut layers = Vec::new();
+        for (name, priority, layer) in LOCAL_SOURCES {
+            match self.config_dirs.as_slice() {
+                [] => layers.push((name.to_string(), priority, layer, LocalSource::new())),
+                [dir] => layers.push((name.to_string(), priority, layer, Ok(open(dir)))),
+                [first, ..] if layer == LocalLayer::Project => {
+                    layers.push((name.to_string(), priority, layer, Ok(open(first))))
+                }
+                dirs => {
+                    for dir in dirs {
+                        let name = format!("{}:{}", name, dir.display());
+                        layers.push((name, priority, layer, Ok(open(dir).with_label(true))));
+                    }
+                }
+            }
+        }
+        layers
+    }
+
+    /// The local configuration changes are written to: the first config
+    /// directory, or the default one
+    fn local_target(&self) -> Result<LocalSource, CliError> {
+        let local = match self.config_dirs.first() {
+            Some(dir) => {
+                LocalSource::with_path(dir.clone()).with_managed_settings(managed_settings_path())
+            }
+            None => return LocalSource::new(),
+        };
+        Ok(match std::env::current_dir() {
+            Ok(cwd) => local.with_current_dir(cwd),
+            Err(_) => local,
+        })
     }
 
+    /// Everything skipped over since the last call: unreadable files, and
+    /// sources that failed when they were optional
+    pub fn take_warnings(&self) -> Vec<Warning> {
+        self.warnings.take()
+    }
+
+    fn warn(&self, warning: Warning) {
+        let mut warnings = self.warnings.borrow_mut();
+        if !warnings.contains(&warning) {
+            warnings.push(warning);
+        }
+    }
+
+    /// Keep the warnings a source collected, labelled with its name
+    fn keep_warnings(&self, registered: &RegisteredSource) {
+        for mut warning in registered.source.take_warnings() {
+            warning.source = registered.name.clone();
+            self.warn(warning);
+        }
+    }
+
+    /// A source failed outright. Unless sources were selected by name, it
+    /// becomes a warning and the other sources are still listed.
+    fn degrade(&self, registered: &RegisteredSource, error: CliError) -> Result<(), CliError> {
+        if !self.selected.is_empty() {
+            return Err(error);
+        }
+        self.warn(Warning::new(registered.name.clone(), error.to_string()));
+        Ok(())
+    }
+
+    /// Concatenate what every source lists, in increasing order of priority
+    fn collect<T>(
+        &self,
+        list: impl Fn(&dyn Source) -> Option<Result<Vec<T>, CliError>>,
+    ) -> Result<Vec<T>, CliError> {
+        let mut items = Vec::new();
+
+        for registered in self.sources()?.iter() {
+            let result = list(registered.source.as_ref());
+            self.keep_warnings(registered);
+            match result {
+                Some(Ok(found)) => items.extend(found),
+                Some(Err(e)) => self.degrade(registered, e)?,
+                None => {}
+            }
+        }
+
+        Ok(items)
+    }
+
+    pub fn list_all(&self) -> Result<ClaudeList, CliError> {
+        Ok(ClaudeList {
+            skills: self.list_skills()?,
+            mcps: self.list_mcps()?,
+            commands: self.list_commands()?,
+            agents: self.list_agents()?,
+            hooks: self.list_hooks()?,
+            plugins: self.list_plugins()?,
+            warnings: self.take_warnings(),
+        })
+    }
+
+    // Sorts are stable, so among equal keys higher-priority sources come last
+
     pub fn list_skills(&self) -> Result<Vec<Skill>, CliError> {
-        let local = LocalSource::new()?;
-        local.list_skills()
+        let mut skills = self.collect(|s| s.skills().map(|s| s.list_skills()))?;
+        skills.sort_by(|a, b| a.name.cmp(&b.name).then(a.scope.cmp(&b.scope)));
+        Ok(merger::merge_skills(skills))
+    }
+
+    /// Estimate each skill's context cost. This reads every SKILL.md and the
+    /// files it links to, so only listings that show the counts ask for it.
+    pub fn estimate_tokens(&self, skills: &mut [Skill]) {
+        for skill in skills {
+            skill.tokens = Some(tokens::estimate_skill(skill));
+        }
     }
 
     pub fn list_mcps(&self) -> Result<Vec<McpServer>, CliError> {
-        let local = LocalSource::new()?;
-        local.list_mcps()
+        let mut mcps = self.collect(|s| s.mcps().map(|s| s.list_mcps()))?;
+        mcps.sort_by(|a, b| a.name.cmp(&b.name).then(a.scope.cmp(&b.scope)));
+        Ok(mcps)
     }
 
-    /// Show detail of a skill or MCP server by name
-    /// Tries to find a skill first, then falls back to MCP server
-    pub fn show(&self, name: &str) -> Result<DetailItem, CliError> {
-        let local = LocalSource::new()?;
+    /// MCP server names defined in more than one file, with the definition
+    /// that wins and how the shadowed ones differ from it
+    pub fn mcp_conflicts(&self) -> Result<Vec<McpConflict>, CliError> {
+        let definitions = self.collect(|s| s.mcps().map(|s| s.list_mcp_definitions(false)))?;
+        Ok(conflicts::find_conflicts(definitions))
+    }
+
+    pub fn list_commands(&self) -> Result<Vec<Command>, CliError> {
+        let mut commands = self.collect(|s| s.commands().map(|s| s.list_commands()))?;
+        commands.sort_by(|a, b| a.name.cmp(&b.name).then(a.scope.cmp(&b.scope)));
+        Ok(commands)
+    }
+
+    pub fn list_agents(&self) -> Result<Vec<Agent>, CliError> {
+        let mut agents = self.collect(|s| s.agents().map(|s| s.list_agents()))?;
+        agents.sort_by(|a, b| a.name.cmp(&b.name).then(a.scope.cmp(&b.scope)));
+        Ok(agents)
+    }
+
+    pub fn list_hooks(&self) -> Result<Vec<Hook>, CliError> {
+        let mut hooks = self.collect(|s| s.hooks().map(|s| s.list_hooks()))?;
+        hooks.sort_by(|a, b| a.event.cmp(&b.event).then(a.scope.cmp(&b.scope)));
+        Ok(hooks)
+    }
+
+    pub fn list_plugins(&self) -> Result<Vec<Plugin>, CliError> {
+        let mut plugins = self.collect(|s| s.plugins().map(|s| s.list_plugins()))?;
+        plugins.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(plugins)
+    }
 
-        // Try to find skill first
-        match local.get_skill_detail(name) {
-            Ok(skill_detail) => return Ok(DetailItem::Skill(skill_detail)),
-            Err(CliError::NotFound(_, _)) => {
-                // Skill not found, try MCP
+    /// Check local skills against the SKILL.md spec, or only the one matching `name`.
+    /// Skills that exist only in the API have no files to check.
+    pub fn lint(&self, name: Option<&str>) -> Result<LintReport, CliError> {
+        let skills: Vec<Skill> = self
+            .list_skills()?
+            .into_iter()
+            .filter(|s| s.path.is_some())
+            .collect();
+        let skills = match name {
+            Some(name) => vec![find_by_name(&skills, name, |s| &s.name)?.clone()],
+            None => skills,
+        };
+
+        let mut report = LintReport::default();
+        for skill in skills {
+            if let Some(ref dir) = skill.path {
+                report.findings.extend(lint::lint_skill(&skill.name, dir));
             }
-            Err(e) => return Err(e),
+            report.skills.push(skill.name);
+        }
+
+        // A SKILL.md that failed to parse while listing is already a finding
+        report.warnings = self
+            .take_warnings()
+            .into_iter()
+            .filter(|w| {
+                !report
+                    .findings
+                    .iter()
+                    .any(|f| w.file.as_ref() == Some(&f.file))
+            })
+            .collect();
+        Ok(report)
+    }
+
+    /// Look for risky things Claude Code could execute: how MCP servers are
+    /// started and configured, what skill scripts do, and where hooks run from
+    pub fn audit(&self) -> Result<AuditReport, CliError> {
+        let mut report = AuditReport {
+            rules: audit::rules(),
+            ..Default::default()
+        };
+        // Shadowed definitions and other projects' servers can still be run
+        let definitions = self.collect(|s| s.mcps().map(|s| s.list_mcp_definitions(true)))?;
+        for mcp in definitions {
+            report.findings.extend(audit::audit_mcp(&mcp));
+        }
+        for skill in self.list_skills()? {
+            report.findings.extend(audit::audit_skill(&skill));
+        }
+        for hook in self.list_hooks()? {
+            report.findings.extend(audit::audit_hook(&hook));
+        }
+
+        report.findings.sort_by(|a, b| {
+            b.risk
+                .cmp(&a.risk)
+                .then_with(|| a.kind.cmp(&b.kind))
+                .then_with(|| a.item.cmp(&b.item))
+                .then_with(|| a.file.cmp(&b.file))
+                .then_with(|| a.line.cmp(&b.line))
+        });
+        report.warnings = self.take_warnings();
+        Ok(report)
+    }
+
+    /// Create a skill from the built-in template in the user or project
+    /// skills directory, where `list_skills` will find it
+    pub fn new_skill(&self, skill: &NewSkill, scope: Scope) -> Result<PathBuf, CliError> {
+        let local = self.local_target()?;
+        let skills_dir = local
+            .skills_dir(scope)
+            .ok_or_else(|| LocalSourceError::ConfigNotFound(PathBuf::from(".claude")))?;
+        skill.create(&skills_dir)
+    }
+
+    /// Copy a skill from a directory, zip archive or git URL into the user or
+    /// project skills directory. A skill of the same name anywhere else is a
+    /// collision unless `force` is set; with `dry_run` nothing is written.
+    pub fn install(
+        &self,
+        source: &str,
+        scope: Scope,
+        force: bool,
+        dry_run: bool,
+    ) -> Result<SkillChange, CliError> {
+        let staged = install::stage(source)?;
+        let local = self.local_target()?;
+        let skills_dir = local
+            .skills_dir(scope)
+            .ok_or_else(|| LocalSourceError::ConfigNotFound(PathBuf::from(".claude")))?;
+        let dest = skills_dir.join(&staged.name);
+
+        let collisions: Vec<Skill> = self
+            .list_skills()?
+            .into_iter()
+            .filter(|s| s.name == staged.name && s.path.as_ref() != Some(&dest))
+            .collect();
+        let replaced = dest.exists();
+        if !force {
+            if replaced {
+                return Err(CliError::AlreadyExists(dest.display().to_string()));
+            }
+            if let Some(skill) = collisions.first() {
+                let location = match skill.path {
+                    Some(ref path) => path.display().to_string(),
+                    None => skill.source.to_string(),
+                };
+                return Err(CliError::AlreadyExists(format!(
+                    "{} skill '{}' at {}",
+                    skill.scope, skill.name, location
+                )));
+            }
+        }
+
+        let files = if dry_run {
+            install::files(&staged.dir)?
+        } else {
+            install::copy_to(&staged, &dest)?
+        };
+        Ok(SkillChange {
+            name: staged.name.clone(),
+            scope,
+            path: dest,
+            files,
+            replaced,
+            collisions,
+            dry_run,
+        })
+    }
+
+    /// Remove a user or project skill, within `scope` if given. `name` must
+    /// match exactly, and a name installed in both scopes is refused rather
+    /// than guessed at. Plugin and API skills cannot be uninstalled here.
+    pub fn uninstall(
+        &self,
+        name: &str,
+        scope: Option<Scope>,
+        dry_run: bool,
+    ) -> Result<SkillChange, CliError> {
+        let skills: Vec<Skill> = self
+            .list_skills()?
+            .into_iter()
+            .filter(|s| s.path.is_some() && s.plugin.is_none())
+            .filter(|s| scope.is_none_or(|scope| s.scope == scope))
+            .collect();
+        let mut matches: Vec<&Skill> = skills.iter().filter(|s| s.name == name).collect();
+        let skill = match matches.len() {
+            0 => {
+                let names = skills.iter().map(|s| s.name.clone()).collect();
+                return Err(CliError::NotFound(name.to_string(), names));
+            }
+            1 => matches.remove(0),
+            _ => {
+                return Err(CliError::Ambiguous(
+                    name.to_string(),
+                    matches
+                        .iter()
+                        .map(|s| {
+                            let path = s.path.as_deref().unwrap_or_else(|| Path::new(""));
+                            format!("{} ({})", s.scope, path.display())
+                        })
+                        .collect(),
+                ))
+            }
+        };
+        let path = skill.path.clone().unwrap_or_default();
+
+        if !dry_run {
+            std::fs::remove_dir_all(&path)?;
+        }
+        Ok(SkillChange {
+            name: skill.name.clone(),
+            scope: skill.scope,
+            path,
+            files: Vec::new(),
+            replaced: false,
+            collisions: Vec::new(),
+            dry_run,
+        })
+    }
+
+    /// Add an MCP server to the file Claude Code reads for `scope`
+    pub fn mcp_add(
+        &self,
+        name: &str,
+        scope: Scope,
+        spec: &McpServerSpec,
+    ) -> Result<McpChange, CliError> {
+        self.edit_mcps(
+            |local| local.add_mcp(name, scope, spec),
+            |mcps, change| has_mcp(mcps, &change.name, change.scope),
+        )
+    }
+
+    /// Remove an MCP server, looking in `scope` or in every editable scope
+    pub fn mcp_remove(&self, name: &str, scope: Option<Scope>) -> Result<McpChange, CliError> {
+        self.edit_mcps(
+            |local| local.remove_mcp(name, scope),
+            |mcps, change| !has_mcp(mcps, &change.name, change.scope),
+        )
+    }
+
+    pub fn mcp_rename(
+        &self,
+        name: &str,
+        new_name: &str,
+        scope: Option<Scope>,
+    ) -> Result<McpChange, CliError> {
+        self.edit_mcps(
+            |local| local.rename_mcp(name, new_name, scope),
+            |mcps, change| {
+                has_mcp(mcps, &change.name, change.scope) && !has_mcp(mcps, name, change.scope)
+            },
+        )
+    }
+
+    /// Enable or disable an MCP server for the current project
+    pub fn mcp_set_enabled(
+        &self,
+        name: &str,
+        enabled: bool,
+        scope: Option<Scope>,
+    ) -> Result<McpChange, CliError> {
+        self.edit_mcps(
+            |local| local.set_mcp_enabled(name, enabled, scope),
+            |mcps, change| {
+                mcps.iter()
+                    .filter(|m| m.name == change.name && m.scope == change.scope)
+                    .all(|m| m.disabled != enabled)
+            },
+        )
+    }
+
+    /// Make an edit, then list the servers again from scratch. If the file
+    /// no longer reads or the edit did not take, the previous file is put back.
+    fn edit_mcps(
+        &self,
+        edit: impl FnOnce(&LocalSource) -> Result<(McpChange, SavedFile), CliError>,
+        took: impl Fn(&[McpServer], &McpChange) -> bool,
+    ) -> Result<McpChange, CliError> {
+        let (change, saved) = edit(&self.local_target()?)?;
+
+        let local = self.local_target()?;
+        let problem = match local.list_mcps() {
+            Ok(mcps) => local
+                .take_warnings()
+                .into_iter()
+                .find(|w| w.file.as_deref() == Some(saved.path.as_path()))
+                .map(|w| w.error)
+                .or_else(|| {
+                    (!took(&mcps, &change)).then(|| {
+                        format!("server '{}' not listed as {}", change.name, change.action)
+                    })
+                }),
+            Err(e) => Some(e.to_string()),
+        };
+
+        match problem {
+            None => Ok(change),
+            Some(problem) => {
+                saved.restore()?;
+                Err(LocalSourceError::InvalidConfig(format!(
+                    "{}: edit rolled back: {}",
+                    saved.path.display(),
+                    problem
+                ))
+                .into())
+            }
+        }
+    }
+
+    /// Start each stdio MCP server and record whether it completes the handshake
+    pub fn check_mcps(&self, mcps: &mut [McpServer]) {
+        health::check_servers(mcps, self.timeout);
+    }
+
+    /// Connect to an MCP server and list the tools, resources and prompts it exposes
+    pub fn inspect_mcp(&self, detail: &mut McpDetail) {
+        health::inspect_server(detail, self.timeout);
+    }
+
+    /// Look up each stdio MCP server's command on PATH and its runtime's version
+    pub fn resolve_mcps(&self, mcps: &mut [McpServer]) {
+        resolve::resolve_servers(mcps, self.timeout);
+    }
+
+    pub fn resolve_mcp(&self, detail: &mut McpDetail) {
+        detail.resolution = resolve::resolve(&detail.config, self.timeout);
+    }
+
+    /// Show detail of a skill, MCP server or agent by name
+    /// Tries to find a skill first, then falls back to MCP server, then agent.
+    pub fn show(&self, name: &str) -> Result<DetailItem, CliError> {
+        let mut suggestions = Vec::new();
+
+        let skill = self.find_detail(
+            name,
+            &mut suggestions,
+            |s| {
+                s.skills()
+                    .map(|s| s.list_skills().map(|items| names(&items, |i| &i.name)))
+            },
+            |s, name| {
+                s.skills().map(|s| {
+                    s.get_skill_detail(name)
+                        .map(|detail| DetailItem::Skill(Box::new(detail)))
+                })
+            },
+        )?;
+        if let Some(detail) = skill {
+            return Ok(detail);
+        }
+
+        let mcp = self.find_detail(
+            name,
+            &mut suggestions,
+            |s| {
+                s.mcps()
+                    .map(|s| s.list_mcps().map(|items| names(&items, |i| &i.name)))
+            },
+            |s, name| {
+                s.mcps().map(|s| {
+                    s.get_mcp_detail(name)
+                        .map(|detail| DetailItem::Mcp(Box::new(detail)))
+                })
+            },
+        )?;
+        if let Some(detail) = mcp {
+            return Ok(detail);
         }
 
-        // Fallback to MCP
-        match local.get_mcp_detail(name) {
-            Ok(mcp_detail) => Ok(DetailItem::Mcp(mcp_detail)),
-            Err(e) => Err(e),
+        let agent = self.find_detail(
+            name,
+            &mut suggestions,
+            |s| {
+                s.agents()
+                    .map(|s| s.list_agents().map(|items| names(&items, |i| &i.name)))
+            },
+            |s, name| {
+                s.agents()
+                    .map(|s| s.get_agent_detail(name).map(DetailItem::Agent))
+            },
+        )?;
+        agent.ok_or_else(|| CliError::NotFound(name.to_string(), suggestions))
+    }
+
+    /// Match `name` once against what every source lists, so an exact match
+    /// anywhere beats a partial one and partial matches in several places are
+    /// ambiguous, then ask the source holding the match for its detail. A
+    /// name listed by several sources is read from the highest-priority one.
+    /// Without a match, the names seen are added to `suggestions`.
+    fn find_detail(
+        &self,
+        name: &str,
+        suggestions: &mut Vec<String>,
+        list: impl Fn(&dyn Source) -> Option<Result<Vec<String>, CliError>>,
+        detail: impl Fn(&dyn Source, &str) -> Option<Result<DetailItem, CliError>>,
+    ) -> Result<Option<DetailItem>, CliError> {
+        let mut candidates: Vec<(&RegisteredSource, String)> = Vec::new();
+        for registered in self.sources()?.iter() {
+            let result = list(registered.source.as_ref());
+            self.keep_warnings(registered);
+            match result {
+                Some(Ok(names)) => {
+                    for found in names {
+                        candidates.retain(|(_, n)| *n != found);
+                        candidates.push((registered, found));
+                    }
+                }
+                Some(Err(e)) => self.degrade(registered, e)?,
+                None => {}
+            }
         }
+
+        let (registered, found) = match find_by_name(&candidates, name, |(_, n)| n) {
+            Ok(&(registered, ref found)) => (registered, found.clone()),
+            Err(CliError::NotFound(_, names)) => {
+                for name in names {
+                    if !suggestions.contains(&name) {
+                        suggestions.push(name);
+                    }
+                }
+                return Ok(None);
+            }
+            Err(e) => return Err(e),
+        };
+
+        let result = detail(registered.source.as_ref(), &found);
+        self.keep_warnings(registered);
+        result.transpose()
     }
 }
 
+/// The names of listed items
+fn names<T>(items: &[T], name: impl Fn(&T) -> &String) -> Vec<String> {
+    items.iter().map(|item| name(item).clone()).collect()
+}
+
 impl Default for ListService {
     fn default() -> Self {
         Self::new()
     }
 }
+
+/// Whether a server called `name` is listed in `scope`
+fn has_mcp(mcps: &[McpServer], name: &str, scope: Scope) -> bool {
+    mcps.iter().any(|m| m.name == name && m.scope == scope)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{SkillDetail, SkillStartMatter, SourceType};
+    use crate::source::json_file::JsonFile;
+    use crate::source::SkillSource;
+    use crate::test_support::TempDir;
+
+    /// Skills held in memory; details say which source answered
+    struct MemorySource {
+        label: &'static str,
+        skills: Vec<Skill>,
+    }
+
+    impl MemorySource {
+        fn new(label: &'static str, names: &[&str]) -> Self {
+            let skills = names.iter().map(|name| Skill::new(*name)).collect();
+            Self { label, skills }
+        }
+    }
+
+    impl Source for MemorySource {
+        fn skills(&self) -> Option<&dyn SkillSource> {
+            Some(self)
+        }
+    }
+
+    impl SkillSource for MemorySource {
+        fn list_skills(&self) -> Result<Vec<Skill>, CliError> {
+            Ok(self.skills.clone())
+        }
+
+        fn get_skill_detail(&self, name: &str) -> Result<SkillDetail, CliError> {
+            let skill = find_by_name(&self.skills, name, |s| &s.name)?;
+            Ok(SkillDetail {
+                name: skill.name.clone(),
+                start_matter: SkillStartMatter::default(),
+                content: self.label.to_string(),
+                path: PathBuf::new(),
+                scope: skill.scope,
+                source: SourceType::Local,
+                version: None,
+                id: None,
+                files: Vec::new(),
+            })
+        }
+    }
+
+    fn service(sources: Vec<(i32, MemorySource)>) -> ListService {
+        let mut registry = SourceRegistry::new();
+        for (priority, source) in sources {
+            registry.register(source.label, priority, source);
+        }
+        ListService::new().with_registry(registry)
+    }
+
+    /// Name and answering source of a skill detail
+    fn shown(service: &ListService, name: &str) -> Result<(String, String), CliError> {
+        match service.show(name)? {
+            DetailItem::Skill(detail) => Ok((detail.name, detail.content)),
+            other => panic!("expected a skill, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn an_exact_match_beats_a_partial_one_in_a_higher_source() {
+        let service = service(vec![
+            (20, MemorySource::new("user", &["pdf"])),
+            (30, MemorySource::new("project", &["pdf-forms"])),
+        ]);
+
+        let (name, source) = shown(&service, "pdf").unwrap();
+        assert_eq!((name.as_str(), source.as_str()), ("pdf", "user"));
+    }
+
+    #[test]
+    fn the_same_name_is_read_from_the_highest_priority_source() {
+        let service = service(vec![
+            (30, MemorySource::new("project", &["pdf"])),
+            (20, MemorySource::new("user", &["pdf"])),
+        ]);
+
+        let (_, source) = shown(&service, "pd").unwrap();
+        assert_eq!(source, "project");
+    }
+
+    #[test]
+    fn partial_matches_across_sources_are_ambiguous() {
+        let service = service(vec![
+            (20, MemorySource::new("user", &["pdf-forms"])),
+            (30, MemorySource::new("project", &["pdf-tables"])),
+        ]);
+
+        match service.show("pdf") {
+            Err(CliError::NotFound(_, suggestions)) => {
+                assert_eq!(suggestions, ["pdf-forms", "pdf-tables"])
+            }
+            other => panic!("expected an ambiguous match, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn selected_sources_are_the_only_ones_searched() {
+        let service = service(vec![
+            (20, MemorySource::new("user", &["pdf"])),
+            (30, MemorySource::new("project", &["docx"])),
+        ])
+        .with_sources(vec!["project".to_string()]);
+
+        assert!(matches!(
+            service.show("pdf"),
+            Err(CliError::NotFound(_, ref names)) if names == &["docx"]
+        ));
+    }
+
+    /// A source holding one installed skill at `path`
+    fn installed(label: &'static str, scope: Scope, name: &str, path: &Path) -> MemorySource {
+        let mut source = MemorySource::new(label, &[name]);
+        source.skills[0].scope = scope;
+        source.skills[0].path = Some(path.to_path_buf());
+        source
+    }
+
+    #[test]
+    fn uninstall_requires_the_exact_name() {
+        let dir = TempDir::new();
+        let service = service(vec![(
+            20,
+            installed("user", Scope::User, "pdf-forms", dir.path()),
+        )]);
+
+        assert!(matches!(
+            service.uninstall("pdf", None, false),
+            Err(CliError::NotFound(_, ref names)) if names == &["pdf-forms"]
+        ));
+        assert!(dir.path().exists());
+    }
+
+    #[test]
+    fn uninstall_refuses_a_name_installed_in_both_scopes() {
+        let user = TempDir::new();
+        let project = TempDir::new();
+        let service = service(vec![
+            (20, installed("user", Scope::User, "pdf", user.path())),
+            (
+                30,
+                installed("project", Scope::Project, "pdf", project.path()),
+            ),
+        ]);
+
+        match service.uninstall("pdf", None, false) {
+            Err(CliError::Ambiguous(_, places)) => assert_eq!(places.len(), 2),
+            other => panic!("expected an ambiguous name, got {:?}", other),
+        }
+        assert!(user.path().exists() && project.path().exists());
+
+        let change = service.uninstall("pdf", Some(Scope::User), false).unwrap();
+        assert_eq!(change.path, user.path());
+        assert!(!user.path().exists() && project.path().exists());
+    }
+
+    /// A service editing `.claude.json` in `dir`, which starts out as `content`
+    fn editing(dir: &TempDir, content: &str) -> (ListService, PathBuf) {
+        let path = dir.write("config/.claude.json", content);
+        let service = ListService::new().with_config_dirs(vec![dir.path().join("config")]);
+        (service, path)
+    }
+
+    #[test]
+    fn an_edit_that_does_not_take_is_rolled_back() {
+        let dir = TempDir::new();
+        let before = r#"{"mcpServers": {"db": {"command": "true"}}}"#;
+        let (service, path) = editing(&dir, before);
+
+        let result = service.edit_mcps(
+            |local| local.add_mcp("web", Scope::User, &McpServerSpec::remote("http://x")),
+            |_, _| false,
+        );
+
+        assert!(matches!(result, Err(CliError::LocalConfigError(_))));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), before);
+    }
+
+    #[test]
+    fn an_edit_that_breaks_the_file_is_rolled_back() {
+        let dir = TempDir::new();
+        let before = r#"{"mcpServers": {"db": {"command": "true"}}}"#;
+        let (service, path) = editing(&dir, before);
+
+        let result = service.edit_mcps(
+            |_| {
+                let mut file = JsonFile::open(&path)?;
+                file.object_mut(&["mcpServers"])?
+                    .insert("web".to_string(), serde_json::json!("not a server"));
+                let saved = file.save()?;
+                let change = McpChange {
+                    action: "added".to_string(),
+                    name: "web".to_string(),
+                    renamed_from: None,
+                    scope: Scope::User,
+                    file: saved.path.clone(),
+                    backup: saved.backup.clone(),
+                };
+                Ok((change, saved))
+            },
+            |_, _| true,
+        );
+
+        assert!(result.is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), before);
+    }
+
+    #[test]
+    fn an_edit_that_takes_is_kept() {
+        let dir = TempDir::new();
+        let (service, path) = editing(&dir, r#"{"mcpServers": {}}"#);
+
+        let change = service
+            .mcp_add("web", Scope::User, &McpServerSpec::remote("http://x"))
+            .unwrap();
+
+        assert_eq!(change.file, path);
+        assert!(std::fs::read_to_string(&path).unwrap().contains("\"web\""));
+    }
+
+    #[test]
+    fn audit_reads_every_project_and_shadowed_definitions() {
+        let dir = TempDir::new();
+        let server = serde_json::json!({ "command": "npx", "args": ["some-server"] });
+        let claude_json = serde_json::json!({
+            "mcpServers": { "shadowed": server },
+            "projects": { "/elsewhere": { "mcpServers": { "other": server } } }
+        });
+        dir.write("config/.claude.json", &claude_json.to_string());
+        dir.write(
+            "config/settings.json",
+            &serde_json::json!({ "mcpServers": { "shadowed": { "command": "true" } } }).to_string(),
+        );
+        let service = ListService::new()
+            .with_sources(vec!["user".to_string(), "project".to_string()])
+            .with_config_dirs(vec![dir.path().join("config")]);
+
+        let report = service.audit().unwrap();
+        let unpinned: Vec<&str> = report
+            .findings
+            .iter()
+            .filter(|f| f.rule == "unpinned-package")
+            .map(|f| f.item.as_str())
+            .collect();
+        assert_eq!(unpinned, ["other", "shadowed"]);
+    }
+}
@@ -0,0 +1,312 @@
+use crate::model::{CommandResolution, McpConfig, McpServer, McpTransport};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Commands that need a runtime, and the runtime whose version is reported
+const RUNTIMES: [(&str, &str); 12] = [
+    ("npx", "node"),
+    ("npm", "node"),
+    ("node", "node"),
+    ("bunx", "bun"),
+    ("bun", "bun"),
+    ("uvx", "uv"),
+    ("uv", "uv"),
+    ("python", "python"),
+    ("python3", "python"),
+    ("deno", "deno"),
+    ("docker", "docker"),
+    ("podman", "podman"),
+];
+
+/// Resolve every stdio server's command concurrently
+pub fn resolve_servers(mcps: &mut [McpServer], timeout: Duration) {
+    thread::scope(|scope| {
+        for mcp in mcps.iter_mut() {
+            scope.spawn(move || {
+                mcp.resolution = mcp.config.as_ref().and_then(|c| resolve(c, timeout));
+            });
+        }
+    });
+}
+
+/// Look the command up on PATH, using the server's own `PATH` from `env`
+/// when it sets one, and run its runtime with `--version`. Only binaries
+/// found through our own PATH are run; paths and `PATH` entries from the
+/// config are only reported. `None` for remote servers.
+pub fn resolve(config: &McpConfig, timeout: Duration) -> Option<CommandResolution> {
+    if config.transport != McpTransport::Stdio {
+        return None;
+    }
+    let command = config.command.as_deref()?;
+    let dirs = search_dirs(config);
+    let found = find_binary(command, &dirs);
+    let executable = found.as_ref().is_some_and(|(path, _)| is_executable(path));
+
+    let name = Path::new(command)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let runtime = runtime_for(&name);
+    let (checked, runtime_version) = match (runtime, &found) {
+        (Some(runtime), Some(found)) if executable => {
+            // Launchers such as `npx` and `uvx` need their runtime on PATH
+            // too; the interpreters report their own version
+            let (binary, trusted) = if runtime == name || runtime == "python" {
+                found.clone()
+            } else {
+                find_binary(runtime, &dirs).unwrap_or_else(|| found.clone())
+            };
+            if trusted {
+                (true, version(&binary, timeout))
+            } else {
+                (false, None)
+            }
+        }
+        _ => (false, None),
+    };
+
+    Some(CommandResolution {
+        command: command.to_string(),
+        path: found.map(|(path, _)| path),
+        executable,
+        runtime: runtime.map(str::to_string),
+        runtime_version,
+        checked,
+    })
+}
+
+/// `python3.12` counts as `python`
+fn runtime_for(name: &str) -> Option<&'static str> {
+    let name = name.strip_suffix(".exe").unwrap_or(name);
+    RUNTIMES
+        .iter()
+        .find(|(command, _)| {
+            name == *command
+                || (*command == "python"
+                    && name
+                        .strip_prefix("python3.")
+                        .is_some_and(|minor| minor.chars().all(|c| c.is_ascii_digit())))
+        })
+        .map(|&(_, runtime)| runtime)
+}
+
+/// Directories to search, each with whether binaries found there may be
+/// run: the server's `PATH` override with `${PATH}`/`$PATH` expanded, or
+/// ours. Only absolute directories from our own PATH are trusted.
+fn search_dirs(config: &McpConfig) -> Vec<(PathBuf, bool)> {
+    let inherited: Vec<(PathBuf, bool)> = std::env::var_os("PATH")
+        .map(|path| {
+            std::env::split_paths(&path)
+                .map(|dir| {
+                    let trusted = dir.is_absolute();
+                    (dir, trusted)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    match config.env.as_ref().and_then(|env| env.get("PATH")) {
+        Some(custom) => std::env::split_paths(custom)
+            .flat_map(|dir| match dir.to_str() {
+                Some("$PATH" | "${PATH}") => inherited.clone(),
+                _ => vec![(dir, false)],
+            })
+            .collect(),
+        None => inherited,
+    }
+}
+
+/// Commands with a slash are paths, relative to the working directory
+/// Claude Code starts the server in; others are searched for in `dirs`.
+/// A path the config names is never trusted, absolute or not.
+fn find_binary(command: &str, dirs: &[(PathBuf, bool)]) -> Option<(PathBuf, bool)> {
+    if command.contains('/') || command.contains(std::path::MAIN_SEPARATOR) {
+        let path = Path::new(command);
+        let path = if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            std::env::current_dir().ok()?.join(path)
+        };
+        return path.is_file().then_some((path, false));
+    }
+
+    dirs.iter()
+        .filter(|(dir, _)| !dir.as_os_str().is_empty())
+        .flat_map(|(dir, trusted)| {
+            candidates(dir, command)
+                .into_iter()
+                .map(move |candidate| (candidate, *trusted))
+        })
+        .find(|(candidate, _)| candidate.is_file())
+}
+
+#[cfg(windows)]
+fn candidates(dir: &Path, command: &str) -> Vec<PathBuf> {
+    let extensions = std::env::var("PATHEXT").unwrap_or_else(|_| ".EXE;.CMD;.BAT".to_string());
+    std::iter::once(dir.join(command))
+        .chain(
+            extensions
+                .split(';')
+                .map(|ext| dir.join(format!("{}{}", command, ext))),
+        )
+        .collect()
+}
+
+#[cfg(not(windows))]
+fn candidates(dir: &Path, command: &str) -> Vec<PathBuf> {
+    vec![dir.join(command)]
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path).is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|m| m.is_file())
+}
+
+/// First line of `<binary> --version`, or `None` when it fails or takes
+/// longer than `timeout`. The server's env is not passed on: only our own
+/// `PATH`, so scripts can find their interpreter.
+fn version(binary: &Path, timeout: Duration) -> Option<String> {
+    let mut command = Command::new(binary);
+    command
+        .arg("--version")
+        .env_clear()
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if let Some(path) = std::env::var_os("PATH") {
+        command.env("PATH", path);
+    }
+    let mut child = command.spawn().ok()?;
+
+    let deadline = Instant::now() + timeout;
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => break,
+            Ok(None) if Instant::now() < deadline => thread::sleep(Duration::from_millis(20)),
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+        }
+    }
+
+    let output = child.wait_with_output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    // Python 2 printed its version to stderr
+    let text = if output.stdout.is_empty() {
+        output.stderr
+    } else {
+        output.stdout
+    };
+    String::from_utf8_lossy(&text)
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+    use std::collections::HashMap;
+    use std::os::unix::fs::PermissionsExt;
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    /// A `node` in `dir` that leaves a marker when run and prints `v$FOO`
+    fn fake_node(dir: &TempDir) -> PathBuf {
+        let marker = dir.path().join("ran");
+        let script = format!("#!/bin/sh\ntouch '{}'\necho \"v$FOO\"\n", marker.display());
+        let path = dir.write("bin/node", &script);
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    fn stdio(command: &str, env: &[(&str, String)]) -> McpConfig {
+        McpConfig {
+            transport: McpTransport::Stdio,
+            command: Some(command.to_string()),
+            args: None,
+            env: Some(
+                env.iter()
+                    .map(|(k, v)| (k.to_string(), v.clone()))
+                    .collect::<HashMap<_, _>>(),
+            ),
+        }
+    }
+
+    #[test]
+    fn absolute_commands_are_only_reported() {
+        let dir = TempDir::new();
+        let node = fake_node(&dir);
+        let config = stdio(&node.display().to_string(), &[]);
+
+        let resolution = resolve(&config, TIMEOUT).unwrap();
+        assert_eq!(resolution.path.as_deref(), Some(node.as_path()));
+        assert!(resolution.executable);
+        assert!(!resolution.checked);
+        assert!(resolution.runtime_version.is_none());
+        assert!(!dir.path().join("ran").exists());
+    }
+
+    #[test]
+    fn missing_absolute_commands_are_reported() {
+        let dir = TempDir::new();
+        let config = stdio(&dir.path().join("node").display().to_string(), &[]);
+
+        let resolution = resolve(&config, TIMEOUT).unwrap();
+        assert!(resolution.path.is_none());
+        assert!(resolution.problem().unwrap().ends_with("not found"));
+    }
+
+    #[test]
+    fn versions_are_read_without_the_server_env() {
+        let dir = TempDir::new();
+        let node = fake_node(&dir);
+
+        // `FOO` is only in the config, which `version` does not see
+        assert_eq!(version(&node, TIMEOUT).as_deref(), Some("v"));
+        assert!(dir.path().join("ran").exists());
+    }
+
+    #[test]
+    fn binaries_on_a_config_path_are_only_reported() {
+        let dir = TempDir::new();
+        let node = fake_node(&dir);
+        let path = dir.path().join("bin").display().to_string();
+        let config = stdio("node", &[("PATH", path)]);
+
+        let resolution = resolve(&config, TIMEOUT).unwrap();
+        assert_eq!(resolution.path.as_deref(), Some(node.as_path()));
+        assert!(resolution.executable);
+        assert!(!resolution.checked);
+        assert!(resolution.problem().is_none());
+        assert!(!dir.path().join("ran").exists());
+    }
+
+    #[test]
+    fn config_path_entries_are_untrusted_and_path_expands_to_ours() {
+        let ours = search_dirs(&stdio("node", &[]));
+        let config = stdio("node", &[("PATH", "/opt/node/bin:${PATH}".to_string())]);
+
+        let dirs = search_dirs(&config);
+        assert_eq!(dirs[0], (PathBuf::from("/opt/node/bin"), false));
+        assert_eq!(dirs[1..], ours[..]);
+
+        let replaced = search_dirs(&stdio("node", &[("PATH", "/usr/bin".to_string())]));
+        assert_eq!(replaced, [(PathBuf::from("/usr/bin"), false)]);
+    }
+}
@@ -0,0 +1,127 @@
+use crate::error::CliError;
+use crate::service::lint::{name_problems, DESCRIPTION_MAX_LEN};
+use serde::Serialize;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+const DEFAULT_DESCRIPTION: &str =
+    "TODO: describe what this skill does and when Claude should use it";
+
+/// A skill to create from the built-in template
+#[derive(Debug, Clone)]
+pub struct NewSkill {
+    name: String,
+    description: Option<String>,
+    scripts: bool,
+    references: bool,
+}
+
+#[derive(Serialize)]
+struct Frontmatter<'a> {
+    name: &'a str,
+    description: &'a str,
+}
+
+impl NewSkill {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            scripts: false,
+            references: false,
+        }
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Also create an empty `scripts/` directory
+    pub fn with_scripts(mut self, scripts: bool) -> Self {
+        self.scripts = scripts;
+        self
+    }
+
+    /// Also create an empty `references/` directory
+    pub fn with_references(mut self, references: bool) -> Self {
+        self.references = references;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Reject what `lint` would report as an error
+    pub fn validate(&self) -> Result<(), CliError> {
+        let mut problems: Vec<String> = name_problems(&self.name)
+            .into_iter()
+            .map(|(_, message)| message)
+            .collect();
+        if let Some(ref description) = self.description {
+            if description.trim().is_empty() {
+                problems.push("description is empty".to_string());
+            } else if description.chars().count() > DESCRIPTION_MAX_LEN {
+                problems.push(format!(
+                    "description is longer than {} characters",
+                    DESCRIPTION_MAX_LEN
+                ));
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(CliError::InvalidName(self.name.clone(), problems))
+        }
+    }
+
+    /// Create `<skills_dir>/<name>/SKILL.md`, returning the skill directory.
+    /// An existing skill directory is never touched.
+    pub fn create(&self, skills_dir: &Path) -> Result<PathBuf, CliError> {
+        self.validate()?;
+
+        let dir = skills_dir.join(&self.name);
+        fs::create_dir_all(skills_dir)?;
+        fs::create_dir(&dir).map_err(|e| match e.kind() {
+            ErrorKind::AlreadyExists => CliError::AlreadyExists(dir.display().to_string()),
+            _ => e.into(),
+        })?;
+
+        fs::write(dir.join("SKILL.md"), self.render()?)?;
+        if self.scripts {
+            fs::create_dir(dir.join("scripts"))?;
+        }
+        if self.references {
+            fs::create_dir(dir.join("references"))?;
+        }
+        Ok(dir)
+    }
+
+    /// SKILL.md from the template
+    fn render(&self) -> Result<String, CliError> {
+        // Let serde_yaml quote descriptions containing `:` or `#`
+        let frontmatter = serde_yaml::to_string(&Frontmatter {
+            name: &self.name,
+            description: self.description.as_deref().unwrap_or(DEFAULT_DESCRIPTION),
+        })
+        .map_err(|e| CliError::Io(std::io::Error::new(ErrorKind::InvalidData, e)))?;
+
+        let mut body = format!(
+            "# {}\n\n## Instructions\n\nStep-by-step guidance for Claude to follow.\n\n## Examples\n\n- An example request and how to handle it\n",
+            self.name
+        );
+        if self.scripts {
+            body.push_str("\n## Scripts\n\nHelper scripts live in `scripts/`.\n");
+        }
+        if self.references {
+            body.push_str(
+                "\n## References\n\nDetailed documentation lives in `references/`; read it only when needed.\n",
+            );
+        }
+
+        Ok(format!("---\n{}---\n\n{}", frontmatter, body))
+    }
+}
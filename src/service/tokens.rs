@@ -0,0 +1,110 @@
+use crate::model::{Skill, SkillTokens};
+use crate::service::lint::references;
+use crate::source::frontmatter::parse_skill_md_full;
+use std::fs;
+use std::path::Path;
+
+/// Rough characters per token for English prose and code
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Referenced files larger than this are estimated from their size unread
+const MAX_REFERENCE_BYTES: u64 = 1024 * 1024;
+
+/// Approximate token count of `text`
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(CHARS_PER_TOKEN)
+}
+
+/// Estimate what a skill costs in context: its name and description are
+/// always loaded, the body and referenced files only when the skill is used.
+/// Skills without a local SKILL.md only have a metadata estimate.
+pub fn estimate_skill(skill: &Skill) -> SkillTokens {
+    let metadata = format!(
+        "{}: {}",
+        skill.name,
+        skill.description.as_deref().unwrap_or("")
+    );
+    let mut tokens = SkillTokens {
+        metadata: estimate_tokens(&metadata),
+        body: None,
+        references: None,
+    };
+
+    let dir = match skill.path {
+        Some(ref dir) => dir,
+        None => return tokens,
+    };
+    let content = match fs::read_to_string(dir.join("SKILL.md")) {
+        Ok(content) => content,
+        Err(_) => return tokens,
+    };
+    let body = match parse_skill_md_full(&content) {
+        Ok((_, body)) => body,
+        Err(_) => content,
+    };
+    tokens.body = Some(estimate_tokens(&body));
+
+    let mut targets: Vec<_> = references(&body).into_iter().map(|(_, t)| t).collect();
+    targets.sort();
+    targets.dedup();
+    // Binary and missing files, and links leaving the skill, are not counted
+    tokens.references = Some(
+        targets
+            .iter()
+            .filter_map(|target| estimate_file(dir, target))
+            .sum(),
+    );
+    tokens
+}
+
+/// Tokens in `target`, relative to the skill directory `dir`, when it is a
+/// text file inside it
+fn estimate_file(dir: &Path, target: &Path) -> Option<usize> {
+    let dir = dir.canonicalize().ok()?;
+    // Resolves `..` and symlinks, so either can be checked against `dir`
+    let path = dir.join(target).canonicalize().ok()?;
+    if !path.starts_with(&dir) {
+        return None;
+    }
+    let size = fs::metadata(&path).ok().filter(|m| m.is_file())?.len();
+    if size > MAX_REFERENCE_BYTES {
+        return Some((size as usize).div_ceil(CHARS_PER_TOKEN));
+    }
+    fs::read_to_string(&path)
+        .ok()
+        .map(|text| estimate_tokens(&text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn counts_body_and_references_inside_the_skill() {
+        let dir = TempDir::new();
+        dir.write(
+            "pdf/SKILL.md",
+            "---\nname: pdf\ndescription: Fill forms\n---\nSee [forms](forms.md), \
+             [secret](../secret.txt) and [key](/etc/hostname).\n",
+        );
+        dir.write("pdf/forms.md", &"x".repeat(400));
+        dir.write("secret.txt", &"y".repeat(4000));
+
+        let mut skill = Skill::new("pdf").with_path(dir.path().join("pdf"));
+        skill.description = Some("Fill forms".to_string());
+        let tokens = estimate_skill(&skill);
+
+        assert_eq!(tokens.metadata, estimate_tokens("pdf: Fill forms"));
+        assert!(tokens.body.unwrap() > 0);
+        assert_eq!(tokens.references, Some(100));
+    }
+
+    #[test]
+    fn skills_without_a_directory_only_have_metadata() {
+        let tokens = estimate_skill(&Skill::new("remote"));
+
+        assert_eq!(tokens.body, None);
+        assert_eq!(tokens.references, None);
+    }
+}
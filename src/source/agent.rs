@@ -0,0 +1,119 @@
+use crate::error::CliError;
+use crate::model::{Agent, AgentDetail, Scope};
+use crate::source::frontmatter::{parse_frontmatter, split_list};
+use crate::source::local::LocalLayer;
+use crate::source::{find_by_name, AgentSource, LocalSource};
+use std::fs;
+use std::path::Path;
+
+impl AgentSource for LocalSource {
+    fn list_agents(&self) -> Result<Vec<Agent>, CliError> {
+        let mut agents = Vec::new();
+        if self.reads(LocalLayer::User) {
+            let user_dir = self.claude_dir().join("agents");
+            agents.extend(self.tolerate(&user_dir, self.scan_agents_dir(&user_dir, Scope::User)));
+        }
+
+        if let Some(project_dir) = self
+            .project_claude_dir()
+            .filter(|_| self.reads(LocalLayer::Project))
+        {
+            let project_dir = project_dir.join("agents");
+            let result = self.scan_agents_dir(&project_dir, Scope::Project);
+            agents.extend(self.tolerate(&project_dir, result));
+        }
+
+        agents.sort_by(|a, b| a.name.cmp(&b.name).then(a.scope.cmp(&b.scope)));
+        self.label(&mut agents, |a| &mut a.config_dir);
+        Ok(agents)
+    }
+
+    fn get_agent_detail(&self, name: &str) -> Result<AgentDetail, CliError> {
+        let agents = self.list_agents()?;
+        let agent = find_by_name(&agents, name, |a| &a.name)?;
+
+        let content = fs::read_to_string(&agent.path)?;
+        let (_, body) = parse_frontmatter(&content);
+
+        Ok(AgentDetail {
+            agent: agent.clone(),
+            content: body,
+        })
+    }
+}
+
+impl LocalSource {
+    /// Read every `*.md` file in an `agents/` directory. A file that cannot be
+    /// read is skipped with a warning naming it.
+    pub(crate) fn scan_agents_dir(&self, dir: &Path, scope: Scope) -> Result<Vec<Agent>, CliError> {
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut agents = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if !path.is_file() || path.extension().and_then(|e| e.to_str()) != Some("md") {
+                continue;
+            }
+
+            let read = fs::read_to_string(&path).map(Some).map_err(CliError::from);
+            let content = match self.tolerate(&path, read) {
+                Some(content) => content,
+                None => continue,
+            };
+            let (fields, _) = parse_frontmatter(&content);
+
+            // Fall back to the file name when the frontmatter has no name
+            let name = match fields.get("name") {
+                Some(name) if !name.is_empty() => name.clone(),
+                _ => match path.file_stem() {
+                    Some(stem) => stem.to_string_lossy().to_string(),
+                    None => continue,
+                },
+            };
+
+            agents.push(Agent {
+                name,
+                description: fields.get("description").cloned(),
+                tools: fields
+                    .get("tools")
+                    .map(|v| split_list(v))
+                    .unwrap_or_default(),
+                model: fields.get("model").cloned(),
+                color: fields.get("color").cloned(),
+                scope,
+                path,
+                config_dir: None,
+            });
+        }
+
+        Ok(agents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn an_unreadable_agent_only_hides_itself() {
+        let dir = TempDir::new();
+        dir.write(
+            "agents/reviewer.md",
+            "---\nname: reviewer\ndescription: Reviews code\n---\nBody",
+        );
+        let broken = dir.path().join("agents/broken.md");
+        fs::write(&broken, b"---\nname: \xff\xfe\n---\n").unwrap();
+
+        let source = LocalSource::with_path(dir.path().to_path_buf());
+        let agents = source.list_agents().unwrap();
+
+        let names: Vec<&str> = agents.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, ["reviewer"]);
+        let warnings = source.take_warnings();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].file.as_deref(), Some(broken.as_path()));
+    }
+}
@@ -0,0 +1,532 @@
+use crate::error::{ApiSourceError, CliError};
+use crate::model::{Skill, SkillDetail, SkillStartMatter, SourceType};
+use crate::source::{find_by_name, SkillSource};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::env;
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+/// Environment variable holding the API key
+pub const API_KEY_VAR: &str = "CLAUDE_API_KEY";
+/// Environment variable overriding the API base URL
+pub const BASE_URL_VAR: &str = "CLAUDE_API_BASE_URL";
+pub const DEFAULT_BASE_URL: &str = "https://api.anthropic.com";
+
+const API_VERSION: &str = "2023-06-01";
+const SKILLS_BETA: &str = "skills-2025-10-02";
+
+/// Version lookups sent at the same time when listing
+const CONCURRENT_REQUESTS: usize = 8;
+
+/// Most pages of skills followed before giving up on the listing
+const MAX_PAGES: usize = 100;
+
+/// A GET request to the API
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+    pub url: String,
+    pub query: Vec<(String, String)>,
+    pub headers: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+/// The transport `ApiSource` sends its requests through.
+/// Non-2xx responses are returned as `Ok`; only transport failures are errors.
+pub trait HttpClient: Send + Sync {
+    fn get(&self, request: &HttpRequest) -> Result<HttpResponse, ApiSourceError>;
+}
+
+/// Blocking HTTP client backed by `ureq`
+pub struct UreqClient {
+    agent: ureq::Agent,
+}
+
+impl UreqClient {
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new().timeout(timeout).build(),
+        }
+    }
+}
+
+impl HttpClient for UreqClient {
+    fn get(&self, request: &HttpRequest) -> Result<HttpResponse, ApiSourceError> {
+        let mut req = self.agent.get(&request.url);
+        for (key, value) in &request.query {
+            req = req.query(key, value);
+        }
+        for (key, value) in &request.headers {
+            req = req.set(key, value);
+        }
+
+        let response = match req.call() {
+            Ok(response) => response,
+            Err(ureq::Error::Status(_, response)) => response,
+            Err(e) => return Err(ApiSourceError::Request(e.to_string())),
+        };
+
+        let status = response.status();
+        let body = response
+            .into_string()
+            .map_err(|e| ApiSourceError::Request(e.to_string()))?;
+        Ok(HttpResponse { status, body })
+    }
+}
+
+/// `GET /v1/skills`
+#[derive(Debug, Deserialize)]
+struct SkillPage {
+    data: Vec<ApiSkill>,
+    #[serde(default)]
+    has_more: bool,
+    next_page: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiSkill {
+    id: String,
+    display_title: Option<String>,
+    latest_version: Option<String>,
+}
+
+/// `GET /v1/skills/{id}/versions/{version}`
+#[derive(Debug, Deserialize)]
+struct ApiSkillVersion {
+    version: Option<String>,
+    name: Option<String>,
+    description: Option<String>,
+    directory: Option<String>,
+}
+
+/// Reads skills uploaded to the workspace through the Skills API
+pub struct ApiSource {
+    api_key: String,
+    base_url: String,
+    client: Box<dyn HttpClient>,
+}
+
+impl ApiSource {
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            api_key: api_key.into(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            client: Box::new(UreqClient::new(Duration::from_secs(30))),
+        }
+    }
+
+    /// Build from `CLAUDE_API_KEY`, honouring `CLAUDE_API_BASE_URL` when set
+    pub fn from_env() -> Result<Self, CliError> {
+        let api_key = env::var(API_KEY_VAR)
+            .ok()
+            .filter(|key| !key.is_empty())
+            .ok_or(CliError::MissingApiKey)?;
+
+        let mut source = Self::new(api_key);
+        if let Some(base_url) = env::var(BASE_URL_VAR).ok().filter(|u| !u.is_empty()) {
+            source = source.with_base_url(base_url);
+        }
+        Ok(source)
+    }
+
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_client(mut self, client: impl HttpClient + 'static) -> Self {
+        self.client = Box::new(client);
+        self
+    }
+
+    /// Every skill in the workspace, following `next_page` until exhausted.
+    /// A page seen before ends the listing; more than `MAX_PAGES` is an error.
+    fn fetch_skills(&self) -> Result<Vec<ApiSkill>, CliError> {
+        let mut skills = Vec::new();
+        let mut page: Option<String> = None;
+        let mut seen = HashSet::new();
+
+        for _ in 0..MAX_PAGES {
+            let query = match page {
+                Some(ref p) => vec![("page", p.as_str())],
+                None => Vec::new(),
+            };
+            let response: SkillPage = self.get_json("/v1/skills", &query)?;
+            skills.extend(response.data);
+
+            match response.next_page {
+                Some(next) if response.has_more && seen.insert(next.clone()) => {
+                    page = Some(next);
+                }
+                _ => return Ok(skills),
+            }
+        }
+
+        Err(
+            ApiSourceError::InvalidResponse(format!("/v1/skills: more than {} pages", MAX_PAGES))
+                .into(),
+        )
+    }
+
+    /// Metadata of the latest version, which carries the name and description
+    fn fetch_latest_version(&self, skill: &ApiSkill) -> Result<Option<ApiSkillVersion>, CliError> {
+        match skill.latest_version {
+            Some(ref version) => {
+                let path = format!("/v1/skills/{}/versions/{}", skill.id, version);
+                self.get_json(&path, &[]).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Latest version metadata of each skill, fetched a few at a time
+    fn fetch_latest_versions(
+        &self,
+        skills: &[ApiSkill],
+    ) -> Result<Vec<Option<ApiSkillVersion>>, CliError> {
+        let mut versions = Vec::with_capacity(skills.len());
+        for chunk in skills.chunks(CONCURRENT_REQUESTS) {
+            thread::scope(|scope| {
+                let requests: Vec<_> = chunk
+                    .iter()
+                    .map(|skill| scope.spawn(move || self.fetch_latest_version(skill)))
+                    .collect();
+                for request in requests {
+                    versions.push(
+                        request
+                            .join()
+                            .unwrap_or_else(|e| std::panic::resume_unwind(e))?,
+                    );
+                }
+                Ok::<_, CliError>(())
+            })?;
+        }
+        Ok(versions)
+    }
+
+    /// Workspace skills sorted by name, with the metadata of their latest version
+    fn load_skills(&self) -> Result<Vec<(Skill, Option<ApiSkillVersion>)>, CliError> {
+        let api_skills = self.fetch_skills()?;
+        let versions = self.fetch_latest_versions(&api_skills)?;
+        let mut skills = Vec::new();
+
+        for (api_skill, version) in api_skills.into_iter().zip(versions) {
+            let name = version
+                .as_ref()
+                .and_then(|v| v.name.clone())
+                .or_else(|| api_skill.display_title.clone())
+                .unwrap_or_else(|| api_skill.id.clone());
+
+            let mut skill = Skill::new(name)
+                .with_source(SourceType::Api)
+                .with_id(api_skill.id);
+            skill.version = version
+                .as_ref()
+                .and_then(|v| v.version.clone())
+                .or(api_skill.latest_version);
+            skill.description = version.as_ref().and_then(|v| v.description.clone());
+            skills.push((skill, version));
+        }
+
+        skills.sort_by(|a, b| a.0.name.cmp(&b.0.name));
+        Ok(skills)
+    }
+
+    fn get_json<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, &str)],
+    ) -> Result<T, CliError> {
+        let request = HttpRequest {
+            url: format!("{}{}", self.base_url, path),
+            query: query
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            headers: vec![
+                ("x-api-key".to_string(), self.api_key.clone()),
+                ("anthropic-version".to_string(), API_VERSION.to_string()),
+                ("anthropic-beta".to_string(), SKILLS_BETA.to_string()),
+            ],
+        };
+
+        let response = self.client.get(&request)?;
+        if !(200..300).contains(&response.status) {
+            return Err(ApiSourceError::Status {
+                code: response.status,
+                message: error_message(&response.body),
+            }
+            .into());
+        }
+
+        serde_json::from_str(&response.body)
+            .map_err(|e| ApiSourceError::InvalidResponse(format!("{}: {}", path, e)).into())
+    }
+}
+
+impl SkillSource for ApiSource {
+    fn list_skills(&self) -> Result<Vec<Skill>, CliError> {
+        Ok(self
+            .load_skills()?
+            .into_iter()
+            .map(|(skill, _)| skill)
+            .collect())
+    }
+
+    fn get_skill_detail(&self, name: &str) -> Result<SkillDetail, CliError> {
+        let skills = self.load_skills()?;
+        let (skill, version) = find_by_name(&skills, name, |(s, _)| &s.name)?;
+        let directory = version.as_ref().and_then(|v| v.directory.clone());
+
+        // The API only returns metadata; SKILL.md itself is not downloaded
+        Ok(SkillDetail {
+            name: skill.name.clone(),
+            start_matter: SkillStartMatter {
+                name: Some(skill.name.clone()),
+                description: skill.description.clone(),
+                version: skill.version.clone(),
+                ..Default::default()
+            },
+            content: String::new(),
+            path: PathBuf::from(directory.unwrap_or_default()),
+            scope: skill.scope,
+            source: SourceType::Api,
+            version: skill.version.clone(),
+            id: skill.id.clone(),
+            files: Vec::new(),
+        })
+    }
+}
+
+/// Pull `error.message` out of an API error body, falling back to the raw body
+fn error_message(body: &str) -> String {
+    serde_json::from_str::<Value>(body)
+        .ok()
+        .and_then(|v| {
+            v.pointer("/error/message")
+                .and_then(Value::as_str)
+                .map(String::from)
+        })
+        .unwrap_or_else(|| body.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    /// Answers from canned responses keyed by path plus `?page=`, and
+    /// records every request it was sent
+    #[derive(Default)]
+    struct MockClient {
+        responses: HashMap<String, HttpResponse>,
+        requests: Arc<Mutex<Vec<HttpRequest>>>,
+    }
+
+    impl MockClient {
+        fn respond(mut self, key: &str, status: u16, body: Value) -> Self {
+            let body = body.to_string();
+            self.responses
+                .insert(key.to_string(), HttpResponse { status, body });
+            self
+        }
+    }
+
+    impl HttpClient for MockClient {
+        fn get(&self, request: &HttpRequest) -> Result<HttpResponse, ApiSourceError> {
+            self.requests.lock().unwrap().push(request.clone());
+            let path = request.url.trim_start_matches("http://api.test");
+            let key = match request.query.iter().find(|(k, _)| k == "page") {
+                Some((_, page)) => format!("{}?page={}", path, page),
+                None => path.to_string(),
+            };
+            self.responses
+                .get(&key)
+                .cloned()
+                .ok_or_else(|| ApiSourceError::Request(format!("unexpected request {}", key)))
+        }
+    }
+
+    fn source(client: MockClient) -> ApiSource {
+        ApiSource::new("test-key")
+            .with_base_url("http://api.test/")
+            .with_client(client)
+    }
+
+    fn version(name: &str, description: &str) -> Value {
+        serde_json::json!({
+            "version": "1759178010641129",
+            "name": name,
+            "description": description,
+            "directory": name,
+        })
+    }
+
+    #[test]
+    fn follows_pages_and_reads_each_latest_version() {
+        let client = MockClient::default()
+            .respond(
+                "/v1/skills",
+                200,
+                serde_json::json!({
+                    "data": [{ "id": "skill_b", "latest_version": "1759178010641129" }],
+                    "has_more": true,
+                    "next_page": "p2",
+                }),
+            )
+            .respond(
+                "/v1/skills?page=p2",
+                200,
+                serde_json::json!({
+                    "data": [
+                        { "id": "skill_a", "latest_version": "1759178010641129" },
+                        { "id": "skill_c", "display_title": "Untitled" },
+                    ],
+                    "has_more": false,
+                }),
+            )
+            .respond(
+                "/v1/skills/skill_a/versions/1759178010641129",
+                200,
+                version("alpha", "First"),
+            )
+            .respond(
+                "/v1/skills/skill_b/versions/1759178010641129",
+                200,
+                version("beta", "Second"),
+            );
+        let requests = Arc::clone(&client.requests);
+
+        let skills = source(client).list_skills().unwrap();
+
+        let names: Vec<&str> = skills.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["Untitled", "alpha", "beta"]);
+        assert_eq!(skills[1].id.as_deref(), Some("skill_a"));
+        assert_eq!(skills[1].description.as_deref(), Some("First"));
+        assert_eq!(skills[1].version.as_deref(), Some("1759178010641129"));
+        assert_eq!(skills[0].source, SourceType::Api);
+
+        let requests = requests.lock().unwrap();
+        // Two pages and one version each for the skills that have one
+        assert_eq!(requests.len(), 4);
+        let headers = &requests[0].headers;
+        assert!(headers.contains(&("x-api-key".to_string(), "test-key".to_string())));
+        assert!(headers.contains(&("anthropic-beta".to_string(), SKILLS_BETA.to_string())));
+    }
+
+    /// A page of one skill without a version, pointing at `next`
+    fn page(id: &str, next: &str) -> Value {
+        serde_json::json!({
+            "data": [{ "id": id, "display_title": id }],
+            "has_more": true,
+            "next_page": next,
+        })
+    }
+
+    #[test]
+    fn pages_that_cycle_end_the_listing() {
+        let client = MockClient::default()
+            .respond("/v1/skills", 200, page("first", "a"))
+            .respond("/v1/skills?page=a", 200, page("second", "b"))
+            .respond("/v1/skills?page=b", 200, page("third", "a"));
+        let requests = Arc::clone(&client.requests);
+
+        let skills = source(client).list_skills().unwrap();
+
+        let names: Vec<&str> = skills.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["first", "second", "third"]);
+        assert_eq!(requests.lock().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn endless_pages_are_an_error() {
+        let mut client = MockClient::default().respond("/v1/skills", 200, page("s0", "p1"));
+        for i in 1..=MAX_PAGES {
+            let next = format!("p{}", i + 1);
+            let key = format!("/v1/skills?page=p{}", i);
+            client = client.respond(&key, 200, page(&format!("s{}", i), &next));
+        }
+
+        assert!(matches!(
+            source(client).list_skills(),
+            Err(CliError::ApiError(ApiSourceError::InvalidResponse(_)))
+        ));
+    }
+
+    #[test]
+    fn error_statuses_carry_the_api_message() {
+        let client = MockClient::default().respond(
+            "/v1/skills",
+            401,
+            serde_json::json!({ "error": { "type": "authentication_error", "message": "invalid x-api-key" } }),
+        );
+
+        match source(client).list_skills() {
+            Err(CliError::ApiError(ApiSourceError::Status { code, message })) => {
+                assert_eq!(code, 401);
+                assert_eq!(message, "invalid x-api-key");
+            }
+            other => panic!("expected a status error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn malformed_bodies_are_invalid_responses() {
+        let client =
+            MockClient::default().respond("/v1/skills", 200, serde_json::json!({ "items": [] }));
+
+        assert!(matches!(
+            source(client).list_skills(),
+            Err(CliError::ApiError(ApiSourceError::InvalidResponse(_)))
+        ));
+    }
+
+    #[test]
+    fn a_failed_version_lookup_fails_the_listing() {
+        let client = MockClient::default()
+            .respond(
+                "/v1/skills",
+                200,
+                serde_json::json!({ "data": [{ "id": "skill_a", "latest_version": "1" }] }),
+            )
+            .respond(
+                "/v1/skills/skill_a/versions/1",
+                500,
+                serde_json::json!({ "error": { "message": "overloaded" } }),
+            );
+
+        assert!(matches!(
+            source(client).list_skills(),
+            Err(CliError::ApiError(ApiSourceError::Status { code: 500, .. }))
+        ));
+    }
+
+    #[test]
+    fn detail_uses_the_version_directory() {
+        let client = MockClient::default()
+            .respond(
+                "/v1/skills",
+                200,
+                serde_json::json!({ "data": [{ "id": "skill_a", "latest_version": "1759178010641129" }] }),
+            )
+            .respond(
+                "/v1/skills/skill_a/versions/1759178010641129",
+                200,
+                version("alpha", "First"),
+            );
+
+        let detail = source(client).get_skill_detail("alpha").unwrap();
+        assert_eq!(detail.path, PathBuf::from("alpha"));
+        assert_eq!(detail.start_matter.description.as_deref(), Some("First"));
+        assert!(detail.content.is_empty());
+    }
+}
@@ -0,0 +1,118 @@
+use crate::error::CliError;
+use crate::model::{Command, Scope};
+use crate::source::frontmatter::{parse_frontmatter, split_list};
+use crate::source::local::LocalLayer;
+use crate::source::{CommandSource, LocalSource};
+use std::fs;
+use std::path::Path;
+
+impl CommandSource for LocalSource {
+    fn list_commands(&self) -> Result<Vec<Command>, CliError> {
+        let mut commands = Vec::new();
+        if self.reads(LocalLayer::User) {
+            let user_dir = self.claude_dir().join("commands");
+            let result = self.scan_commands_dir(&user_dir, &[], Scope::User, &mut commands);
+            self.tolerate(&user_dir, result);
+        }
+
+        if let Some(project_dir) = self
+            .project_claude_dir()
+            .filter(|_| self.reads(LocalLayer::Project))
+        {
+            let project_dir = project_dir.join("commands");
+            let result = self.scan_commands_dir(&project_dir, &[], Scope::Project, &mut commands);
+            self.tolerate(&project_dir, result);
+        }
+
+        commands.sort_by(|a, b| a.name.cmp(&b.name).then(a.scope.cmp(&b.scope)));
+        self.label(&mut commands, |c| &mut c.config_dir);
+        Ok(commands)
+    }
+}
+
+impl LocalSource {
+    /// Recursively collect `*.md` commands. Subdirectories become namespaces,
+    /// so `commands/frontend/component.md` is `frontend:component`. A file that
+    /// cannot be read is skipped with a warning naming it.
+    pub(crate) fn scan_commands_dir(
+        &self,
+        dir: &Path,
+        namespace: &[String],
+        scope: Scope,
+        commands: &mut Vec<Command>,
+    ) -> Result<(), CliError> {
+        if !dir.exists() {
+            return Ok(());
+        }
+
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let path = entry.path();
+
+            if path.is_dir() {
+                let mut nested = namespace.to_vec();
+                nested.push(entry.file_name().to_string_lossy().to_string());
+                self.scan_commands_dir(&path, &nested, scope, commands)?;
+                continue;
+            }
+
+            if path.extension().and_then(|e| e.to_str()) != Some("md") {
+                continue;
+            }
+
+            let stem = match path.file_stem() {
+                Some(stem) => stem.to_string_lossy().to_string(),
+                None => continue,
+            };
+            let mut parts = namespace.to_vec();
+            parts.push(stem);
+
+            let read = fs::read_to_string(&path).map(Some).map_err(CliError::from);
+            let content = match self.tolerate(&path, read) {
+                Some(content) => content,
+                None => continue,
+            };
+            let (fields, _) = parse_frontmatter(&content);
+
+            commands.push(Command {
+                name: parts.join(":"),
+                description: fields.get("description").cloned(),
+                argument_hint: fields.get("argument-hint").cloned(),
+                allowed_tools: fields
+                    .get("allowed-tools")
+                    .map(|v| split_list(v))
+                    .unwrap_or_default(),
+                model: fields.get("model").cloned(),
+                scope,
+                path,
+                config_dir: None,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn an_unreadable_command_only_hides_itself() {
+        let dir = TempDir::new();
+        dir.write("commands/a.md", "---\ndescription: First\n---\n");
+        dir.write("commands/git/z.md", "Last");
+        let broken = dir.path().join("commands/git/broken.md");
+        fs::write(&broken, b"\xff\xfe").unwrap();
+
+        let source = LocalSource::with_path(dir.path().to_path_buf());
+        let commands = source.list_commands().unwrap();
+
+        let names: Vec<&str> = commands.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["a", "git:z"]);
+        let warnings = source.take_warnings();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].file.as_deref(), Some(broken.as_path()));
+    }
+}
@@ -0,0 +1,283 @@
+use crate::error::FrontmatterError;
+use crate::model::SkillStartMatter;
+use std::collections::HashMap;
+
+/// Parse the YAML frontmatter of a SKILL.md, returning it with the body.
+/// Content without frontmatter is all body.
+pub(crate) fn parse_skill_md_full(
+    content: &str,
+) -> Result<(SkillStartMatter, String), FrontmatterError> {
+    let (yaml, body) = match split_frontmatter(content)? {
+        Some(parts) => parts,
+        None => return Ok((SkillStartMatter::default(), content.to_string())),
+    };
+
+    if yaml.trim().is_empty() {
+        return Ok((SkillStartMatter::default(), body));
+    }
+
+    let mut start_matter: SkillStartMatter =
+        serde_yaml::from_str(yaml).map_err(|e| FrontmatterError {
+            // The YAML starts on the line after the opening `---`
+            line: e.location().map(|l| l.line() + 1),
+            message: strip_location(&e.to_string()),
+        })?;
+
+    // Block scalars such as `description: >` keep their trailing newline
+    let strings = start_matter.name.iter_mut();
+    for value in strings.chain(start_matter.description.iter_mut()) {
+        *value = value.trim().to_string();
+    }
+
+    // The Agent Skills spec keeps the version under `metadata`
+    if start_matter.version.is_none() {
+        start_matter.version = start_matter
+            .extra
+            .get("metadata")
+            .and_then(|m| m.get("version"))
+            .and_then(scalar_string);
+    }
+
+    Ok((start_matter, body))
+}
+
+/// Split markdown into its frontmatter fields and body.
+/// This is a lenient line-based reader for commands and agents, whose
+/// frontmatter is often not strictly valid YAML. Multi-line values are joined
+/// with spaces; list items keep one per line.
+pub(crate) fn parse_frontmatter(content: &str) -> (HashMap<String, String>, String) {
+    let mut fields = HashMap::new();
+
+    let (frontmatter, body) = match split_frontmatter(content) {
+        Ok(Some(parts)) => parts,
+        // No or malformed frontmatter, return entire content as body
+        _ => return (fields, content.to_string()),
+    };
+
+    // Parse frontmatter fields
+    let mut current_key: Option<&str> = None;
+    let mut current_value = String::new();
+
+    for line in frontmatter.lines() {
+        let trimmed = line.trim();
+
+        // Check for new key
+        if let Some((key, value)) = trimmed.split_once(':') {
+            // Save previous key if exists
+            if let Some(k) = current_key {
+                fields.insert(k.to_string(), clean_value(&current_value));
+            }
+
+            current_key = Some(key.trim());
+            current_value = value.trim().to_string();
+        } else if current_key.is_some()
+            && (trimmed.starts_with('-') || (!trimmed.is_empty() && !trimmed.contains(':')))
+        {
+            // Multi-line value or list item
+            if trimmed.starts_with('-') {
+                current_value.push('\n');
+            } else {
+                current_value.push(' ');
+            }
+            current_value.push_str(trimmed);
+        }
+    }
+
+    // Save last key
+    if let Some(k) = current_key {
+        fields.insert(k.to_string(), clean_value(&current_value));
+    }
+
+    (fields, body)
+}
+
+/// Split content into the raw frontmatter and the body. The frontmatter must
+/// open on the first line and close with a line that is exactly `---`, so a
+/// `---` inside a value does not end it.
+pub(crate) fn split_frontmatter(content: &str) -> Result<Option<(&str, String)>, FrontmatterError> {
+    let mut lines = content.split_inclusive('\n');
+    match lines.next() {
+        Some(first) if first.trim_end() == "---" => {}
+        _ => return Ok(None),
+    }
+
+    let start = content.find('\n').map(|i| i + 1).unwrap_or(content.len());
+    let mut offset = start;
+    for line in lines {
+        if line.trim_end() == "---" {
+            let frontmatter = &content[start..offset];
+            let body = content[offset + line.len()..].trim_start().to_string();
+            return Ok(Some((frontmatter, body)));
+        }
+        offset += line.len();
+    }
+
+    Err(FrontmatterError {
+        line: Some(1),
+        message: "frontmatter is not closed with '---'".to_string(),
+    })
+}
+
+/// Drop serde_yaml's "at line N column M" positions, which count from the
+/// YAML block rather than the file
+fn strip_location(message: &str) -> String {
+    let mut message = message.to_string();
+    while let Some(i) = message.find(" at line ") {
+        let rest = &message[i + " at line ".len()..];
+        let rest = rest.trim_start_matches(|c: char| c.is_ascii_digit());
+        let rest = rest.strip_prefix(" column ").unwrap_or(rest);
+        let rest = rest.trim_start_matches(|c: char| c.is_ascii_digit());
+        message = format!("{}{}", &message[..i], rest);
+    }
+    message
+}
+
+/// A YAML scalar as a string. Bare numbers go through YAML's float parsing,
+/// so `version: 1.10` becomes `1.1`; quote a version to keep it as written.
+pub(crate) fn scalar_string(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        serde_json::Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// Split a frontmatter list value into its items. Accepts YAML block lists
+/// (`- a` per line), flow lists (`[a, b]`) and comma-separated strings.
+/// Commas inside parentheses, e.g. `Bash(git add:*)`, do not split.
+pub(crate) fn split_list(value: &str) -> Vec<String> {
+    let value = value.trim();
+
+    if value.starts_with('-') {
+        return value
+            .lines()
+            .map(|line| clean_value(line.trim().trim_start_matches('-')))
+            .filter(|item| !item.is_empty())
+            .collect();
+    }
+
+    let value = value
+        .strip_prefix('[')
+        .and_then(|v| v.strip_suffix(']'))
+        .unwrap_or(value);
+
+    let mut items = Vec::new();
+    let mut depth = 0usize;
+    let mut current = String::new();
+    for c in value.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                items.push(clean_value(&current));
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    items.push(clean_value(&current));
+
+    items.retain(|item| !item.is_empty());
+    items
+}
+
+fn clean_value(value: &str) -> String {
+    let trimmed = value.trim();
+    // Remove quotes if present
+    if (trimmed.starts_with('"') && trimmed.ends_with('"') && trimmed.len() > 1)
+        || (trimmed.starts_with('\'') && trimmed.ends_with('\'') && trimmed.len() > 1)
+    {
+        trimmed[1..trimmed.len() - 1].to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(content: &str) -> SkillStartMatter {
+        parse_skill_md_full(content).unwrap().0
+    }
+
+    #[test]
+    fn every_key_is_kept() {
+        let start_matter = parse(
+            "---\nname: pdf\ndescription: Fill forms\nversion: 1.0\nlicense: MIT\n\
+             model: sonnet\nallowed-tools: Read, Bash(git add:*)\nmetadata:\n  owner: docs\n---\nBody\n",
+        );
+
+        assert_eq!(start_matter.name.as_deref(), Some("pdf"));
+        assert_eq!(start_matter.version.as_deref(), Some("1.0"));
+        assert_eq!(start_matter.license.as_deref(), Some("MIT"));
+        assert_eq!(start_matter.model.as_deref(), Some("sonnet"));
+        assert_eq!(start_matter.allowed_tools, ["Read", "Bash(git add:*)"]);
+        assert_eq!(start_matter.extra["metadata"]["owner"], "docs");
+    }
+
+    #[test]
+    fn multi_line_values_may_hold_colons_and_dashes() {
+        let content = "---\nname: pdf\ndescription: >\n  Use when: filling forms\n  --- or tables\n---\n\n# PDF\n";
+        let (start_matter, body) = parse_skill_md_full(content).unwrap();
+
+        assert_eq!(
+            start_matter.description.as_deref(),
+            Some("Use when: filling forms --- or tables")
+        );
+        assert_eq!(body, "# PDF\n");
+    }
+
+    #[test]
+    fn the_version_may_live_under_metadata() {
+        let start_matter = parse("---\nname: pdf\nmetadata:\n  version: \"2.1\"\n---\n");
+        assert_eq!(start_matter.version.as_deref(), Some("2.1"));
+    }
+
+    #[test]
+    fn bare_number_versions_are_read_as_floats() {
+        let start_matter = parse("---\nversion: 1.10\n---\n");
+        assert_eq!(start_matter.version.as_deref(), Some("1.1"));
+        let start_matter = parse("---\nversion: \"1.10\"\n---\n");
+        assert_eq!(start_matter.version.as_deref(), Some("1.10"));
+    }
+
+    #[test]
+    fn allowed_tools_may_be_a_yaml_list() {
+        let start_matter = parse("---\nallowed-tools:\n  - Read\n  - Grep\n---\n");
+        assert_eq!(start_matter.allowed_tools, ["Read", "Grep"]);
+    }
+
+    #[test]
+    fn content_without_frontmatter_is_all_body() {
+        let (start_matter, body) = parse_skill_md_full("# PDF\n---\n").unwrap();
+        assert!(start_matter.name.is_none());
+        assert_eq!(body, "# PDF\n---\n");
+    }
+
+    #[test]
+    fn yaml_errors_carry_the_file_line() {
+        let error =
+            parse_skill_md_full("---\nname: pdf\ndescription: [unclosed\n---\n").unwrap_err();
+        assert_eq!(error.line, Some(3), "{:?}", error);
+        assert!(!error.message.contains(" at line "), "{}", error.message);
+    }
+
+    #[test]
+    fn unclosed_frontmatter_is_an_error() {
+        let error = parse_skill_md_full("---\nname: pdf\n").unwrap_err();
+        assert_eq!(error.line, Some(1));
+    }
+
+    #[test]
+    fn lenient_fields_join_lines_and_split_lists() {
+        let (fields, body) =
+            parse_frontmatter("---\ndescription: Review\n  the diff\ntools: Read, Grep\n---\nBody");
+        assert_eq!(fields["description"], "Review the diff");
+        assert_eq!(split_list(&fields["tools"]), ["Read", "Grep"]);
+        assert_eq!(body, "Body");
+    }
+}
@@ -0,0 +1,84 @@
+use crate::error::{CliError, LocalSourceError};
+use crate::model::{Hook, Scope};
+use crate::source::local::LocalLayer;
+use crate::source::{HookSource, LocalSource};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Deserialize)]
+struct HookSettings {
+    #[serde(default)]
+    hooks: HashMap<String, Vec<HookMatcherConfig>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HookMatcherConfig {
+    matcher: Option<String>,
+    #[serde(default)]
+    hooks: Vec<HookCommandConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HookCommandConfig {
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    command: Option<String>,
+    prompt: Option<String>,
+    timeout: Option<u64>,
+}
+
+impl HookSource for LocalSource {
+    fn list_hooks(&self) -> Result<Vec<Hook>, CliError> {
+        let mut hooks = Vec::new();
+        for (scope, path) in self.settings_files() {
+            // Managed settings are machine-wide, so they belong with the user's
+            let layer = match scope {
+                Scope::Project | Scope::Local => LocalLayer::Project,
+                Scope::User | Scope::Managed => LocalLayer::User,
+            };
+            if !self.reads(layer) {
+                continue;
+            }
+            hooks.extend(self.tolerate(&path, read_hooks(&path, scope)));
+        }
+
+        hooks.sort_by(|a, b| a.event.cmp(&b.event).then(a.scope.cmp(&b.scope)));
+        self.label(&mut hooks, |h| &mut h.config_dir);
+        Ok(hooks)
+    }
+}
+
+/// Read every hook command from the `hooks` object of a settings file
+pub(crate) fn read_hooks(path: &Path, scope: Scope) -> Result<Vec<Hook>, CliError> {
+    let content = fs::read_to_string(path)?;
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let settings: HookSettings = serde_json::from_str(&content)
+        .map_err(|e| LocalSourceError::InvalidConfig(format!("{}: {}", file_name, e)))?;
+
+    let mut hooks = Vec::new();
+    for (event, matchers) in settings.hooks {
+        for matcher in matchers {
+            for config in matcher.hooks {
+                let kind = config.kind.unwrap_or_else(|| "command".to_string());
+                let command = config.command.or(config.prompt).unwrap_or_default();
+                hooks.push(Hook {
+                    event: event.clone(),
+                    matcher: matcher.matcher.clone().filter(|m| !m.is_empty()),
+                    kind,
+                    command,
+                    timeout: config.timeout,
+                    scope,
+                    source: path.to_path_buf(),
+                    config_dir: None,
+                });
+            }
+        }
+    }
+
+    Ok(hooks)
+}
@@ -0,0 +1,200 @@
+use crate::error::{CliError, LocalSourceError};
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A JSON config file edited in place. Keys keep their order and anything
+/// not touched is written back unchanged.
+pub(crate) struct JsonFile {
+    path: PathBuf,
+    value: Value,
+}
+
+/// A file written by `JsonFile::save`, with the copy of what was there before
+#[derive(Debug, Clone)]
+pub(crate) struct SavedFile {
+    pub path: PathBuf,
+    /// `None` when the file did not exist
+    pub backup: Option<PathBuf>,
+}
+
+impl JsonFile {
+    /// Read `path`, starting from an empty object when it does not exist
+    pub fn open(path: &Path) -> Result<Self, CliError> {
+        let value = if path.exists() {
+            let content = fs::read_to_string(path)?;
+            serde_json::from_str(&content).map_err(|e| {
+                LocalSourceError::InvalidConfig(format!("{}: {}", path.display(), e))
+            })?
+        } else {
+            Value::Object(Map::new())
+        };
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            value,
+        })
+    }
+
+    /// The object at `keys`, if every step along the way is an object
+    pub fn object(&self, keys: &[&str]) -> Option<&Map<String, Value>> {
+        keys.iter()
+            .try_fold(&self.value, |value, key| value.get(key))
+            .and_then(Value::as_object)
+    }
+
+    /// The object at `keys`, creating missing steps
+    pub fn object_mut(&mut self, keys: &[&str]) -> Result<&mut Map<String, Value>, CliError> {
+        let path = &self.path;
+        let not_an_object = |key: &str| -> CliError {
+            LocalSourceError::InvalidConfig(format!(
+                "{}: '{}' is not an object",
+                path.display(),
+                key
+            ))
+            .into()
+        };
+
+        let mut value = &mut self.value;
+        let mut parent = "";
+        for key in keys {
+            value = value
+                .as_object_mut()
+                .ok_or_else(|| not_an_object(parent))?
+                .entry(key.to_string())
+                .or_insert_with(|| Value::Object(Map::new()));
+            parent = key;
+        }
+        value.as_object_mut().ok_or_else(|| not_an_object(parent))
+    }
+
+    /// The array of strings at `keys` followed by `list`, creating it if missing
+    pub fn string_list_mut(
+        &mut self,
+        keys: &[&str],
+        list: &str,
+    ) -> Result<&mut Vec<Value>, CliError> {
+        let path = self.path.clone();
+        let value = self
+            .object_mut(keys)?
+            .entry(list.to_string())
+            .or_insert_with(|| Value::Array(Vec::new()));
+        value.as_array_mut().ok_or_else(|| {
+            LocalSourceError::InvalidConfig(format!("{}: '{}' is not a list", path.display(), list))
+                .into()
+        })
+    }
+
+    /// Write the file atomically, keeping the previous contents in `<file>.bak`
+    pub fn save(&self) -> Result<SavedFile, CliError> {
+        let mut content = serde_json::to_string_pretty(&self.value)?;
+        content.push('\n');
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let backup = if self.path.exists() {
+            let backup = with_suffix(&self.path, "bak");
+            fs::copy(&self.path, &backup)?;
+            Some(backup)
+        } else {
+            None
+        };
+
+        // Write next to the target so the rename cannot cross file systems;
+        // `fs::copy` above also carried the permissions over to the backup
+        let temp = with_suffix(&self.path, "tmp");
+        fs::write(&temp, content)?;
+        if let Some(ref backup) = backup {
+            fs::set_permissions(&temp, fs::metadata(backup)?.permissions())?;
+        }
+        if let Err(e) = fs::rename(&temp, &self.path) {
+            let _ = fs::remove_file(&temp);
+            return Err(e.into());
+        }
+
+        Ok(SavedFile {
+            path: self.path.clone(),
+            backup,
+        })
+    }
+}
+
+impl SavedFile {
+    /// Put back what was there before `save`
+    pub fn restore(&self) -> Result<(), CliError> {
+        match self.backup {
+            Some(ref backup) => fs::copy(backup, &self.path).map(|_| ())?,
+            None => fs::remove_file(&self.path)?,
+        }
+        Ok(())
+    }
+}
+
+/// `settings.json` -> `settings.json.bak`
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn save_keeps_key_order_and_untouched_values() {
+        let dir = TempDir::new();
+        let path = dir.write(
+            "settings.json",
+            r#"{"zebra": 1, "apple": {"b": 2, "a": 3}}"#,
+        );
+
+        let mut file = JsonFile::open(&path).unwrap();
+        file.object_mut(&["apple"])
+            .unwrap()
+            .insert("new".to_string(), Value::Bool(true));
+        file.save().unwrap();
+
+        let saved = fs::read_to_string(&path).unwrap();
+        let keys = ["\"zebra\"", "\"apple\"", "\"b\"", "\"a\"", "\"new\""];
+        let positions: Vec<usize> = keys.iter().map(|k| saved.find(k).unwrap()).collect();
+        assert!(positions.windows(2).all(|w| w[0] < w[1]), "{}", saved);
+    }
+
+    #[test]
+    fn save_backs_up_the_previous_contents_and_restore_puts_them_back() {
+        let dir = TempDir::new();
+        let before = r#"{"a": 1}"#;
+        let path = dir.write("settings.json", before);
+
+        let mut file = JsonFile::open(&path).unwrap();
+        file.object_mut(&[])
+            .unwrap()
+            .insert("b".to_string(), Value::from(2));
+        let saved = file.save().unwrap();
+
+        let backup = saved.backup.clone().unwrap();
+        assert_eq!(backup, dir.path().join("settings.json.bak"));
+        assert_eq!(fs::read_to_string(&backup).unwrap(), before);
+        assert!(!dir.path().join("settings.json.tmp").exists());
+
+        saved.restore().unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), before);
+    }
+
+    #[test]
+    fn restoring_a_new_file_removes_it() {
+        let dir = TempDir::new();
+        let path = dir.path().join("nested/.mcp.json");
+
+        let saved = JsonFile::open(&path).unwrap().save().unwrap();
+        assert!(saved.backup.is_none());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{}\n");
+
+        saved.restore().unwrap();
+        assert!(!path.exists());
+    }
+}
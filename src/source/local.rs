@@ -1,149 +1,387 @@
 use crate::error::{CliError, LocalSourceError};
 use crate::model::{
-    ConnectionStatus, McpConfig, McpDetail, McpServer, Skill, SkillDetail, SkillStartMatter,
-    SourceType,
+    McpConfig, McpDetail, McpServer, McpTransport, Scope, Skill, SkillDetail, SkillFile,
+    SkillFileKind, SkillStartMatter, SourceType, Warning,
 };
-use crate::source::{McpSource, SkillSource};
+use crate::source::frontmatter::parse_skill_md_full;
+use crate::source::plugin::{read_json, LoadedPlugin};
+use crate::source::{find_by_name, McpSource, SkillSource};
 use serde::Deserialize;
-use std::collections::HashMap;
+use std::cell::{OnceCell, RefCell};
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// The parts of the local configuration a `LocalSource` reads
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LocalLayer {
+    /// `~/.claude`, `~/.claude.json` and managed settings
+    User,
+    /// The project checkout, plus private per-project entries
+    Project,
+    /// Installed plugins
+    Plugin,
+}
 
 pub struct LocalSource {
     claude_dir: PathBuf,
+    claude_json: PathBuf,
+    current_dir: Option<PathBuf>,
+    project_root: Option<PathBuf>,
+    managed_settings: Option<PathBuf>,
+    all_projects: bool,
+    layers: Vec<LocalLayer>,
+    labelled: bool,
+    warnings: RefCell<Vec<Warning>>,
+    plugins: OnceCell<Vec<LoadedPlugin>>,
 }
 
+const ALL_LAYERS: [LocalLayer; 3] = [LocalLayer::User, LocalLayer::Project, LocalLayer::Plugin];
+
+/// Environment variable Claude Code uses to relocate its config directory
+pub const CONFIG_DIR_VAR: &str = "CLAUDE_CONFIG_DIR";
+
 impl LocalSource {
+    /// Read `$CLAUDE_CONFIG_DIR` when set, otherwise `~/.claude` and
+    /// `~/.claude.json`, for the project containing the current directory
     pub fn new() -> Result<Self, CliError> {
-        let home = std::env::var("HOME")
-            .or_else(|_| std::env::var("USERPROFILE"))
-            .map_err(|_| {
-                CliError::Io(std::io::Error::new(
-                    std::io::ErrorKind::NotFound,
-                    "Home directory not found",
-                ))
-            })?;
-        let claude_dir = PathBuf::from(home).join(".claude");
-        Ok(Self { claude_dir })
+        let source = match std::env::var_os(CONFIG_DIR_VAR).filter(|d| !d.is_empty()) {
+            Some(dir) => {
+                Self::with_path(PathBuf::from(dir)).with_managed_settings(managed_settings_path())
+            }
+            None => {
+                let home = std::env::var("HOME")
+                    .or_else(|_| std::env::var("USERPROFILE"))
+                    .map_err(|_| {
+                        CliError::Io(std::io::Error::new(
+                            std::io::ErrorKind::NotFound,
+                            "Home directory not found",
+                        ))
+                    })?;
+                let home = PathBuf::from(home);
+                let mut source = Self::with_path(home.join(".claude"))
+                    .with_managed_settings(managed_settings_path());
+                source.claude_json = home.join(".claude.json");
+                source
+            }
+        };
+
+        Ok(match std::env::current_dir() {
+            Ok(cwd) => source.with_current_dir(cwd),
+            Err(_) => source,
+        })
     }
 
+    /// Use `path` as the config directory; `.claude.json` is read from inside
+    /// it. No managed settings are read unless `with_managed_settings` is set.
     pub fn with_path(path: PathBuf) -> Self {
-        Self { claude_dir: path }
+        Self {
+            claude_json: path.join(".claude.json"),
+            claude_dir: path,
+            current_dir: None,
+            project_root: None,
+            managed_settings: None,
+            all_projects: false,
+            layers: ALL_LAYERS.to_vec(),
+            labelled: false,
+            warnings: RefCell::default(),
+            plugins: OnceCell::new(),
+        }
+    }
+
+    /// Read managed settings from `path`, the highest-precedence layer
+    pub fn with_managed_settings(mut self, path: Option<PathBuf>) -> Self {
+        self.managed_settings = path;
+        self
+    }
+
+    /// Resolve project files and per-project entries for `dir`
+    pub fn with_current_dir(mut self, dir: PathBuf) -> Self {
+        self.project_root = Some(find_project_root(&dir));
+        self.current_dir = Some(dir);
+        self
+    }
+
+    /// Tag every listed item with the config directory, for combined views
+    /// over several directories
+    pub fn with_label(mut self, labelled: bool) -> Self {
+        self.labelled = labelled;
+        self
+    }
+
+    /// Set `config_dir` on each item when labelling is on. Skills, servers,
+    /// commands, agents, hooks and plugins all carry the field; it names the
+    /// config directory the item was read from when several are listed side
+    /// by side, and is `None` otherwise.
+    pub(crate) fn label<T>(
+        &self,
+        items: &mut [T],
+        config_dir: impl Fn(&mut T) -> &mut Option<PathBuf>,
+    ) {
+        if self.labelled {
+            for item in items {
+                *config_dir(item) = Some(self.claude_dir.clone());
+            }
+        }
+    }
+
+    pub fn with_project_root(mut self, root: PathBuf) -> Self {
+        self.current_dir.get_or_insert_with(|| root.clone());
+        self.project_root = Some(root);
+        self
+    }
+
+    /// Include per-project MCP servers from every project in `~/.claude.json`,
+    /// not just the ones for the current directory.
+    pub fn with_all_projects(mut self, all_projects: bool) -> Self {
+        self.all_projects = all_projects;
+        self
+    }
+
+    /// Only read the given layers; all of them are read by default
+    pub fn with_layers(mut self, layers: &[LocalLayer]) -> Self {
+        self.layers = layers.to_vec();
+        self
+    }
+
+    pub(crate) fn reads(&self, layer: LocalLayer) -> bool {
+        self.layers.contains(&layer)
+    }
+
+    /// Whether a per-project entry keyed by `project` applies to the current
+    /// directory: it names the project root or the directory itself, not an
+    /// ancestor of either
+    pub(crate) fn is_current_project(&self, project: &Path) -> bool {
+        self.project_root.as_deref() == Some(project)
+            || self.current_dir.as_deref() == Some(project)
+    }
+
+    /// Files that could not be read so far. Listing skips them instead of failing.
+    pub fn take_warnings(&self) -> Vec<Warning> {
+        self.warnings.take()
+    }
+
+    /// Unwrap the result of reading `file`, or record a warning and carry on
+    /// with an empty value
+    pub(crate) fn tolerate<T: Default>(&self, file: &Path, result: Result<T, CliError>) -> T {
+        result.unwrap_or_else(|e| {
+            let error = match e {
+                CliError::LocalConfigError(e) => e.to_string(),
+                e => e.to_string(),
+            };
+            let warning = Warning::new("local", error).with_file(file);
+            let mut warnings = self.warnings.borrow_mut();
+            // The same file is read by several listings, e.g. settings.json
+            if !warnings.contains(&warning) {
+                warnings.push(warning);
+            }
+            T::default()
+        })
+    }
+
+    pub(crate) fn loaded_plugins(&self) -> &OnceCell<Vec<LoadedPlugin>> {
+        &self.plugins
+    }
+
+    /// The user-level config directory, `~/.claude` by default
+    pub(crate) fn claude_dir(&self) -> &Path {
+        &self.claude_dir
+    }
+
+    /// `~/.claude.json`, or the one inside `$CLAUDE_CONFIG_DIR`
+    pub(crate) fn claude_json(&self) -> &Path {
+        &self.claude_json
+    }
+
+    /// Root of the project containing the current directory, if any
+    pub(crate) fn project_root(&self) -> Option<&Path> {
+        self.project_root.as_deref()
+    }
+
+    pub(crate) fn current_dir(&self) -> Option<&Path> {
+        self.current_dir.as_deref()
+    }
+
+    /// Settings files in increasing order of precedence, with their scope.
+    /// Files that do not exist are skipped.
+    pub(crate) fn settings_files(&self) -> Vec<(Scope, PathBuf)> {
+        let mut files = vec![(Scope::User, self.claude_dir.join("settings.json"))];
+        if let Some(project_dir) = self.project_claude_dir() {
+            files.push((Scope::Project, project_dir.join("settings.json")));
+            files.push((Scope::Local, project_dir.join("settings.local.json")));
+        }
+        if let Some(ref managed) = self.managed_settings {
+            files.push((Scope::Managed, managed.clone()));
+        }
+        files.retain(|(_, path)| path.exists());
+        files
+    }
+
+    /// Where skills of `scope` live; `None` for scopes without a skills
+    /// directory, or for the project outside of one
+    pub fn skills_dir(&self, scope: Scope) -> Option<PathBuf> {
+        match scope {
+            Scope::User => Some(self.claude_dir.join("skills")),
+            Scope::Project => self.project_claude_dir().map(|dir| dir.join("skills")),
+            Scope::Local | Scope::Managed => None,
+        }
+    }
+
+    /// The project's `.claude` directory, unless it is the user-level one
+    /// (e.g. when running from the home directory).
+    pub(crate) fn project_claude_dir(&self) -> Option<PathBuf> {
+        self.project_root
+            .as_ref()
+            .map(|root| root.join(".claude"))
+            .filter(|dir| dir != &self.claude_dir)
     }
 }
 
+/// Location of the enterprise managed settings file for this platform
+pub fn managed_settings_path() -> Option<PathBuf> {
+    if cfg!(target_os = "macos") {
+        Some(PathBuf::from(
+            "/Library/Application Support/ClaudeCode/managed-settings.json",
+        ))
+    } else if cfg!(target_os = "windows") {
+        Some(PathBuf::from(
+            r"C:\ProgramData\ClaudeCode\managed-settings.json",
+        ))
+    } else if cfg!(unix) {
+        Some(PathBuf::from("/etc/claude-code/managed-settings.json"))
+    } else {
+        None
+    }
+}
+
+/// Walk up from `start` to the enclosing git checkout.
+/// Falls back to `start` itself when it is not inside a git repository.
+fn find_project_root(start: &Path) -> PathBuf {
+    start
+        .ancestors()
+        .find(|dir| dir.join(".git").exists())
+        .unwrap_or(start)
+        .to_path_buf()
+}
+
 #[derive(Debug, Deserialize)]
 struct McpSettings {
     #[serde(rename = "mcpServers", default)]
     mcp_servers: Option<HashMap<String, McpServerConfig>>,
 }
 
+/// The parts of `~/.claude.json` that hold MCP servers
 #[derive(Debug, Deserialize)]
-struct McpServerConfig {
-    command: Option<String>,
-    args: Option<Vec<String>>,
-    env: Option<HashMap<String, String>>,
+struct ClaudeJson {
+    #[serde(rename = "mcpServers", default)]
+    mcp_servers: Option<HashMap<String, McpServerConfig>>,
+    #[serde(default)]
+    projects: HashMap<String, ClaudeJsonProject>,
 }
 
-/// Parse YAML frontmatter from markdown content
-/// Returns the description field from the frontmatter
-fn parse_skill_md(content: &str) -> Option<String> {
-    let (start_matter, _) = parse_skill_md_full(content);
-    start_matter.description
+#[derive(Debug, Deserialize)]
+struct ClaudeJsonProject {
+    #[serde(rename = "mcpServers", default)]
+    mcp_servers: Option<HashMap<String, McpServerConfig>>,
+    /// User and local servers turned off in this project
+    #[serde(rename = "disabledMcpServers", default)]
+    disabled_mcp_servers: Vec<String>,
 }
 
-/// Parse YAML frontmatter and body from markdown content
-fn parse_skill_md_full(content: &str) -> (SkillStartMatter, String) {
-    let mut start_matter = SkillStartMatter::default();
+/// Settings keys that approve `.mcp.json` servers or turn them off
+#[derive(Debug, Default, Deserialize)]
+struct McpjsonSettings {
+    #[serde(rename = "enabledMcpjsonServers", default)]
+    enabled_mcpjson_servers: Vec<String>,
+    #[serde(rename = "disabledMcpjsonServers", default)]
+    disabled_mcpjson_servers: Vec<String>,
+    #[serde(rename = "enableAllProjectMcpServers", default)]
+    enable_all_project_mcp_servers: bool,
+}
 
-    // Check if content starts with "---"
-    if !content.starts_with("---") {
-        // No frontmatter, return entire content as body
-        return (start_matter, content.to_string());
-    }
+#[derive(Debug, Deserialize)]
+pub(crate) struct McpServerConfig {
+    #[serde(rename = "type")]
+    transport: Option<String>,
+    command: Option<String>,
+    args: Option<Vec<String>>,
+    env: Option<HashMap<String, String>>,
+    url: Option<String>,
+    headers: Option<HashMap<String, String>>,
+}
 
-    // Find the end of frontmatter (second "---")
-    let end_marker = match content[3..].find("---") {
-        Some(pos) => pos,
-        None => {
-            // Malformed frontmatter, return entire content
-            return (start_matter, content.to_string());
-        }
-    };
-
-    let frontmatter = &content[3..3 + end_marker];
-    let body_start = 3 + end_marker + 3;
-    let body = content[body_start..].trim_start().to_string();
-
-    // Parse frontmatter fields
-    let mut current_key: Option<&str> = None;
-    let mut current_value = String::new();
-
-    for line in frontmatter.lines() {
-        let trimmed = line.trim();
-
-        // Check for new key
-        if let Some((key, value)) = trimmed.split_once(':') {
-            // Save previous key if exists
-            if let Some(k) = current_key {
-                let cleaned_value = clean_value(&current_value);
-                if k == "name" {
-                    start_matter.name = Some(cleaned_value);
-                } else if k == "description" {
-                    start_matter.description = Some(cleaned_value);
-                }
-            }
+impl From<McpServerConfig> for McpConfig {
+    fn from(config: McpServerConfig) -> Self {
+        let transport = match (config.transport.as_deref(), config.url) {
+            (Some("sse"), url) => McpTransport::Sse {
+                url: url.unwrap_or_default(),
+                headers: config.headers,
+            },
+            (Some("http") | Some("streamable-http"), url) => McpTransport::Http {
+                url: url.unwrap_or_default(),
+                headers: config.headers,
+            },
+            // Entries without a type but with a URL are remote HTTP servers
+            (None, Some(url)) if config.command.is_none() => McpTransport::Http {
+                url,
+                headers: config.headers,
+            },
+            _ => McpTransport::Stdio,
+        };
 
-            current_key = Some(key.trim());
-            current_value = value.trim().to_string();
-        } else if current_key.is_some()
-            && (trimmed.starts_with('-') || (!trimmed.is_empty() && !trimmed.contains(':')))
-        {
-            // Multi-line value or list item
-            if trimmed.starts_with('-') {
-                current_value.push('\n');
-            } else {
-                current_value.push(' ');
-            }
-            current_value.push_str(trimmed);
+        McpConfig {
+            transport,
+            command: config.command,
+            args: config.args,
+            env: config.env,
         }
     }
+}
 
-    // Save last key
-    if let Some(k) = current_key {
-        let cleaned_value = clean_value(&current_value);
-        if k == "name" {
-            start_matter.name = Some(cleaned_value);
-        } else if k == "description" {
-            start_matter.description = Some(cleaned_value);
-        }
+/// Read the `mcpServers` map from a settings-style JSON file.
+/// Returns an empty map when the file does not exist.
+pub(crate) fn read_mcp_servers(path: &Path) -> Result<HashMap<String, McpServerConfig>, CliError> {
+    if !path.exists() {
+        return Ok(HashMap::new());
     }
 
-    (start_matter, body)
+    let content = fs::read_to_string(path)?;
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let settings: McpSettings = serde_json::from_str(&content)
+        .map_err(|e| LocalSourceError::InvalidConfig(format!("{}: {}", file_name, e)))?;
+
+    Ok(settings.mcp_servers.unwrap_or_default())
 }
 
-fn clean_value(value: &str) -> String {
-    let trimmed = value.trim();
-    // Remove quotes if present
-    if (trimmed.starts_with('"') && trimmed.ends_with('"') && trimmed.len() > 1)
-        || (trimmed.starts_with('\'') && trimmed.ends_with('\'') && trimmed.len() > 1)
-    {
-        trimmed[1..trimmed.len() - 1].to_string()
-    } else {
-        trimmed.to_string()
+/// Read `~/.claude.json`, returning `None` when it does not exist
+fn read_claude_json(path: &Path) -> Result<Option<ClaudeJson>, CliError> {
+    if !path.exists() {
+        return Ok(None);
     }
-}
 
-impl SkillSource for LocalSource {
-    fn list_skills(&self) -> Result<Vec<Skill>, CliError> {
-        let skills_dir = self.claude_dir.join("skills");
+    let content = fs::read_to_string(path)?;
+    let claude_json = serde_json::from_str(&content)
+        .map_err(|e| LocalSourceError::InvalidConfig(format!(".claude.json: {}", e)))?;
+    Ok(Some(claude_json))
+}
 
+impl LocalSource {
+    /// Scan a `skills/` directory, one skill per subdirectory.
+    /// A skill whose frontmatter does not parse is still listed, with a warning.
+    pub(crate) fn scan_skills_dir(
+        &self,
+        skills_dir: &Path,
+        scope: Scope,
+    ) -> Result<Vec<Skill>, CliError> {
         if !skills_dir.exists() {
             return Ok(Vec::new());
         }
 
         let mut skills = Vec::new();
-        let entries = fs::read_dir(&skills_dir)?;
+        let entries = fs::read_dir(skills_dir)?;
 
         for entry in entries {
             let entry = entry?;
@@ -151,56 +389,157 @@ impl SkillSource for LocalSource {
             if path.is_dir() {
                 let name = entry.file_name().to_string_lossy().to_string();
 
-                // Try to read SKILL.md for description
+                // Try to read SKILL.md for description and version
                 let skill_md_path = path.join("SKILL.md");
-                let description = if skill_md_path.exists() {
-                    fs::read_to_string(&skill_md_path)
-                        .ok()
-                        .and_then(|c| parse_skill_md(&c))
+                let start_matter = if skill_md_path.exists() {
+                    let parsed =
+                        read_skill_md(&skill_md_path).map(|(start_matter, _)| start_matter);
+                    self.tolerate(&skill_md_path, parsed)
                 } else {
-                    None
+                    Default::default()
                 };
 
                 skills.push(Skill {
                     name,
-                    version: None,
+                    version: start_matter.version,
                     source: SourceType::Local,
+                    scope,
                     path: Some(path),
-                    description,
+                    description: start_matter.description,
+                    plugin: None,
+                    id: None,
+                    local: None,
+                    api: None,
+                    drift: false,
+                    config_dir: None,
+                    tokens: None,
                 });
             }
         }
 
-        skills.sort_by(|a, b| a.name.cmp(&b.name));
         Ok(skills)
     }
+}
 
-    fn get_skill_detail(&self, name: &str) -> Result<SkillDetail, CliError> {
-        let skills = self.list_skills()?;
-        let name_lower = name.to_lowercase();
+/// Read a SKILL.md and split it into its frontmatter and body
+fn read_skill_md(path: &Path) -> Result<(SkillStartMatter, String), CliError> {
+    let content = fs::read_to_string(path)?;
+    let parsed = parse_skill_md_full(&content)
+        .map_err(|e| LocalSourceError::InvalidFrontmatter(path.to_path_buf(), e))?;
+    Ok(parsed)
+}
 
-        // Find matching skill (case-insensitive partial match)
-        let matches: Vec<&Skill> = skills
-            .iter()
-            .filter(|s| s.name.to_lowercase().contains(&name_lower))
-            .collect();
+/// Extensions of files treated as scripts outside `scripts/`
+const SCRIPT_EXTENSIONS: [&str; 8] = ["sh", "bash", "py", "js", "ts", "rb", "pl", "ps1"];
+
+/// Every file under a skill directory, sorted by path. Symlinked directories
+/// are not followed.
+fn list_skill_files(dir: &Path) -> Result<Vec<SkillFile>, CliError> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+
+    while let Some(current) = pending.pop() {
+        for entry in fs::read_dir(&current)? {
+            let entry = entry?;
+            let path = entry.path();
+            let metadata = fs::symlink_metadata(&path)?;
+            if metadata.is_dir() {
+                if entry.file_name() != ".git" {
+                    pending.push(path);
+                }
+                continue;
+            }
 
-        if matches.is_empty() {
-            let all_names: Vec<String> = skills.iter().map(|s| s.name.clone()).collect();
-            return Err(CliError::NotFound(name.to_string(), all_names));
+            let relative = path.strip_prefix(dir).unwrap_or(&path).to_path_buf();
+            // Report the target's size for symlinked files
+            let metadata = fs::metadata(&path).unwrap_or(metadata);
+            files.push(SkillFile {
+                kind: skill_file_kind(&relative),
+                size: metadata.len(),
+                executable: is_executable(&metadata),
+                path: relative,
+            });
         }
+    }
+
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+/// Classify by the conventional top-level directory, then by extension
+pub(crate) fn skill_file_kind(relative: &Path) -> SkillFileKind {
+    if relative == Path::new("SKILL.md") {
+        return SkillFileKind::Skill;
+    }
+
+    let top = relative
+        .components()
+        .next()
+        .filter(|_| relative.components().count() > 1)
+        .map(|c| c.as_os_str().to_string_lossy().to_lowercase());
+    match top.as_deref() {
+        Some("scripts") => return SkillFileKind::Script,
+        Some("references") | Some("reference") | Some("docs") => return SkillFileKind::Reference,
+        Some("assets") | Some("templates") => return SkillFileKind::Asset,
+        _ => {}
+    }
+
+    let extension = relative
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    if SCRIPT_EXTENSIONS.contains(&extension.as_str()) {
+        SkillFileKind::Script
+    } else if extension == "md" || extension == "txt" {
+        SkillFileKind::Reference
+    } else {
+        SkillFileKind::Asset
+    }
+}
 
-        if matches.len() > 1 {
-            // If multiple matches, check for exact match first
-            if let Some(exact) = matches.iter().find(|s| s.name.to_lowercase() == name_lower) {
-                return self.load_skill_detail(exact);
+#[cfg(unix)]
+fn is_executable(metadata: &fs::Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    metadata.permissions().mode() & 0o111 != 0
+}
+
+#[cfg(not(unix))]
+fn is_executable(_metadata: &fs::Metadata) -> bool {
+    false
+}
+
+impl SkillSource for LocalSource {
+    fn list_skills(&self) -> Result<Vec<Skill>, CliError> {
+        let mut skills = Vec::new();
+        for (scope, layer) in [
+            (Scope::User, LocalLayer::User),
+            (Scope::Project, LocalLayer::Project),
+        ] {
+            if let Some(dir) = self.skills_dir(scope).filter(|_| self.reads(layer)) {
+                skills.extend(self.tolerate(&dir, self.scan_skills_dir(&dir, scope)));
             }
-            // Otherwise return ambiguous match error with suggestions
-            let suggestions: Vec<String> = matches.iter().map(|s| s.name.clone()).collect();
-            return Err(CliError::NotFound(name.to_string(), suggestions));
         }
 
-        self.load_skill_detail(matches[0])
+        // Skills shipped by enabled plugins
+        if self.reads(LocalLayer::Plugin) {
+            skills.extend(self.plugin_skills()?);
+        }
+
+        // Within a scope, the user's own copy sorts after (and so wins over) a plugin's
+        skills.sort_by(|a, b| {
+            a.name
+                .cmp(&b.name)
+                .then(a.scope.cmp(&b.scope))
+                .then(a.plugin.is_none().cmp(&b.plugin.is_none()))
+        });
+        self.label(&mut skills, |s| &mut s.config_dir);
+        Ok(skills)
+    }
+
+    fn get_skill_detail(&self, name: &str) -> Result<SkillDetail, CliError> {
+        let skills = self.list_skills()?;
+        let skill = find_by_name(&skills, name, |s| &s.name)?;
+        self.load_skill_detail(skill)
     }
 }
 
@@ -212,119 +551,212 @@ impl LocalSource {
             .ok_or_else(|| LocalSourceError::ConfigNotFound(PathBuf::from("SKILL.md")))?
             .join("SKILL.md");
 
-        let content = fs::read_to_string(&skill_md_path)?;
-        let (start_matter, body) = parse_skill_md_full(&content);
+        let (start_matter, body) = match read_skill_md(&skill_md_path) {
+            Ok(parsed) => parsed,
+            // Broken frontmatter still shows the file as written
+            Err(e @ CliError::LocalConfigError(LocalSourceError::InvalidFrontmatter(..))) => {
+                self.tolerate::<()>(&skill_md_path, Err(e));
+                let content = fs::read_to_string(&skill_md_path)?;
+                (SkillStartMatter::default(), content)
+            }
+            Err(e) => return Err(e),
+        };
+
+        let dir = skill_md_path.parent().unwrap_or(&skill_md_path);
+        let files = self.tolerate(dir, list_skill_files(dir));
 
         Ok(SkillDetail {
             name: skill.name.clone(),
             start_matter,
             content: body,
             path: skill_md_path,
+            scope: skill.scope,
+            source: skill.source.clone(),
+            version: skill.version.clone(),
+            id: None,
+            files,
         })
     }
 }
 
 impl McpSource for LocalSource {
     fn list_mcps(&self) -> Result<Vec<McpServer>, CliError> {
-        let mut mcps = Vec::new();
-
-        // Try to read from settings.json
-        let settings_path = self.claude_dir.join("settings.json");
-        if settings_path.exists() {
-            let content = fs::read_to_string(&settings_path)?;
-            let settings: McpSettings = serde_json::from_str(&content)
-                .map_err(|e| LocalSourceError::InvalidConfig(format!("settings.json: {}", e)))?;
-
-            if let Some(servers) = settings.mcp_servers {
-                for (name, config) in servers {
-                    mcps.push(McpServer {
-                        name,
-                        status: ConnectionStatus::Unknown,
-                        config: Some(McpConfig {
-                            command: config.command,
-                            args: config.args,
-                            env: config.env,
-                        }),
-                        source: SourceType::Local,
-                    });
-                }
-            }
-        }
-
-        // Also try mcp.json
-        let mcp_path = self.claude_dir.join("mcp.json");
-        if mcp_path.exists() {
-            let content = fs::read_to_string(&mcp_path)?;
-            let settings: McpSettings = serde_json::from_str(&content)
-                .map_err(|e| LocalSourceError::InvalidConfig(format!("mcp.json: {}", e)))?;
-
-            if let Some(servers) = settings.mcp_servers {
-                for (name, config) in servers {
-                    // Avoid duplicates
-                    if !mcps.iter().any(|m| m.name == name) {
-                        mcps.push(McpServer {
-                            name,
-                            status: ConnectionStatus::Unknown,
-                            config: Some(McpConfig {
-                                command: config.command,
-                                args: config.args,
-                                env: config.env,
-                            }),
-                            source: SourceType::Local,
-                        });
-                    }
-                }
-            }
-        }
+        let mut mcps = self.mcp_definitions(self.all_projects)?;
+
+        // settings.json takes precedence over mcp.json, and both over
+        // ~/.claude.json; the other user-level definitions are shadowed
+        let mut seen = HashSet::new();
+        mcps.retain(|m| {
+            m.scope != Scope::User || m.plugin.is_some() || seen.insert(m.name.clone())
+        });
+
+        mcps.sort_by(|a, b| {
+            a.name
+                .cmp(&b.name)
+                .then(a.scope.cmp(&b.scope))
+                .then(a.project.cmp(&b.project))
+                .then(a.plugin.is_none().cmp(&b.plugin.is_none()))
+        });
+        self.label(&mut mcps, |m| &mut m.config_dir);
+        Ok(mcps)
+    }
 
-        mcps.sort_by(|a, b| a.name.cmp(&b.name));
+    fn list_mcp_definitions(&self, all_projects: bool) -> Result<Vec<McpServer>, CliError> {
+        let mut mcps = self.mcp_definitions(all_projects)?;
+        self.label(&mut mcps, |m| &mut m.config_dir);
         Ok(mcps)
     }
 
     fn get_mcp_detail(&self, name: &str) -> Result<McpDetail, CliError> {
         let mcps = self.list_mcps()?;
-        let name_lower = name.to_lowercase();
+        let mcp = find_by_name(&mcps, name, |m| &m.name)?;
+        self.load_mcp_detail(mcp)
+    }
+}
 
-        // Find matching MCP (case-insensitive partial match)
-        let matches: Vec<&McpServer> = mcps
+impl LocalSource {
+    /// Every server definition in the files read, in order of precedence
+    /// within each scope, shadowed ones included. Per-project servers are
+    /// only read for the current project unless `all_projects` is set.
+    fn mcp_definitions(&self, all_projects: bool) -> Result<Vec<McpServer>, CliError> {
+        let mut mcps: Vec<McpServer> = Vec::new();
+
+        // settings.json takes precedence over mcp.json
+        let user_files = [
+            self.claude_dir.join("settings.json"),
+            self.claude_dir.join("mcp.json"),
+        ];
+        for path in user_files.iter().filter(|_| self.reads(LocalLayer::User)) {
+            for (name, config) in self.tolerate(path, read_mcp_servers(path)) {
+                mcps.push(
+                    McpServer::new(name)
+                        .with_config(config.into())
+                        .with_scope(Scope::User)
+                        .with_origin(path.clone()),
+                );
+            }
+        }
+
+        let claude_json = if self.reads(LocalLayer::User) || self.reads(LocalLayer::Project) {
+            self.tolerate(&self.claude_json, read_claude_json(&self.claude_json))
+        } else {
+            None
+        };
+        let mut projects = HashMap::new();
+        if let Some(claude_json) = claude_json {
+            // User servers registered via `claude mcp add --scope user`
+            let user_servers = claude_json
+                .mcp_servers
+                .filter(|_| self.reads(LocalLayer::User));
+            for (name, config) in user_servers.unwrap_or_default() {
+                mcps.push(
+                    McpServer::new(name)
+                        .with_config(config.into())
+                        .with_scope(Scope::User)
+                        .with_origin(self.claude_json.clone()),
+                );
+            }
+            if self.reads(LocalLayer::Project) {
+                projects = claude_json.projects;
+            }
+        }
+
+        let disabled_here: Vec<String> = projects
             .iter()
-            .filter(|m| m.name.to_lowercase().contains(&name_lower))
+            .filter(|(project, _)| self.is_current_project(Path::new(project)))
+            .flat_map(|(_, entry)| entry.disabled_mcp_servers.iter().cloned())
             .collect();
 
-        if matches.is_empty() {
-            let all_names: Vec<String> = mcps.iter().map(|m| m.name.clone()).collect();
-            return Err(CliError::NotFound(name.to_string(), all_names));
+        // Private per-project servers registered via `claude mcp add` (local scope)
+        for (project, entry) in projects {
+            let project = PathBuf::from(project);
+            if !all_projects && !self.is_current_project(&project) {
+                continue;
+            }
+            for (name, config) in entry.mcp_servers.unwrap_or_default() {
+                mcps.push(
+                    McpServer::new(name)
+                        .with_config(config.into())
+                        .with_scope(Scope::Local)
+                        .with_project(project.clone())
+                        .with_origin(self.claude_json.clone()),
+                );
+            }
         }
 
-        if matches.len() > 1 {
-            if let Some(exact) = matches.iter().find(|m| m.name.to_lowercase() == name_lower) {
-                return self.load_mcp_detail(exact);
+        // Project-scoped servers from .mcp.json at the project root
+        if let Some(root) = self
+            .project_root
+            .as_ref()
+            .filter(|_| self.reads(LocalLayer::Project))
+        {
+            let path = root.join(".mcp.json");
+            for (name, config) in self.tolerate(&path, read_mcp_servers(&path)) {
+                mcps.push(
+                    McpServer::new(name)
+                        .with_config(config.into())
+                        .with_scope(Scope::Project)
+                        .with_origin(path.clone()),
+                );
             }
-            let suggestions: Vec<String> = matches.iter().map(|m| m.name.clone()).collect();
-            return Err(CliError::NotFound(name.to_string(), suggestions));
         }
 
-        self.load_mcp_detail(matches[0])
+        // Servers shipped by enabled plugins
+        if self.reads(LocalLayer::Plugin) {
+            mcps.extend(self.plugin_mcps()?);
+        }
+
+        let mcpjson = self.mcpjson_settings();
+        for mcp in mcps.iter_mut().filter(|m| m.plugin.is_none()) {
+            mcp.unapproved = mcp.scope == Scope::Project
+                && !mcpjson.enable_all_project_mcp_servers
+                && !mcpjson.enabled_mcpjson_servers.contains(&mcp.name);
+            mcp.disabled = match mcp.scope {
+                Scope::Project => mcpjson.disabled_mcpjson_servers.contains(&mcp.name),
+                Scope::User | Scope::Local => disabled_here.contains(&mcp.name),
+                Scope::Managed => false,
+            };
+        }
+
+        Ok(mcps)
+    }
+
+    /// `.mcp.json` approvals across settings files
+    fn mcpjson_settings(&self) -> McpjsonSettings {
+        let mut merged = McpjsonSettings::default();
+        for (_, path) in self.settings_files() {
+            let settings: Option<McpjsonSettings> = self.tolerate(&path, read_json(&path));
+            if let Some(settings) = settings {
+                merged
+                    .enabled_mcpjson_servers
+                    .extend(settings.enabled_mcpjson_servers);
+                merged
+                    .disabled_mcpjson_servers
+                    .extend(settings.disabled_mcpjson_servers);
+                merged.enable_all_project_mcp_servers |= settings.enable_all_project_mcp_servers;
+            }
+        }
+        merged
     }
-}
 
-impl LocalSource {
     fn load_mcp_detail(&self, mcp: &McpServer) -> Result<McpDetail, CliError> {
         let config = mcp
             .config
             .clone()
             .ok_or_else(|| CliError::NotFound(mcp.name.clone(), vec![]))?;
 
-        // Determine source path and type
-        let settings_path = self.claude_dir.join("settings.json");
-        let mcp_path = self.claude_dir.join("mcp.json");
-
-        let (source_path, source_type) = if settings_path.exists() {
-            (settings_path.clone(), "settings.json".to_string())
-        } else if mcp_path.exists() {
-            (mcp_path.clone(), "mcp.json".to_string())
-        } else {
-            (self.claude_dir.clone(), "unknown".to_string())
+        let source_path = mcp
+            .origin
+            .clone()
+            .unwrap_or_else(|| self.claude_dir.clone());
+        let file_name = source_path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "unknown".to_string());
+        let source_type = match (&mcp.plugin, mcp.scope) {
+            (Some(plugin), _) => format!("plugin {}", plugin),
+            (None, Scope::Local) => format!("{} (project)", file_name),
+            (None, _) => file_name,
         };
 
         Ok(McpDetail {
@@ -332,6 +764,63 @@ impl LocalSource {
             config,
             source_path,
             source_type,
+            scope: mcp.scope,
+            project: mcp.project.clone(),
+            status: mcp.status.clone(),
+            health: mcp.health.clone(),
+            disabled: mcp.disabled,
+            unapproved: mcp.unapproved,
+            capabilities: None,
+            resolution: mcp.resolution.clone(),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn per_project_entries_only_apply_to_the_exact_project() {
+        let dir = TempDir::new();
+        let parent = dir.path().join("work");
+        let project = parent.join("repo");
+        fs::create_dir_all(project.join(".git")).unwrap();
+        let server = serde_json::json!({ "command": "true" });
+        let claude_json = serde_json::json!({
+            "projects": {
+                parent.display().to_string(): {
+                    "mcpServers": { "parent": server },
+                    "disabledMcpServers": ["own"]
+                },
+                project.display().to_string(): { "mcpServers": { "own": server } }
+            }
+        });
+        dir.write("config/.claude.json", &claude_json.to_string());
+
+        let source =
+            LocalSource::with_path(dir.path().join("config")).with_current_dir(project.join("src"));
+        let mcps = source.list_mcps().unwrap();
+
+        let names: Vec<&str> = mcps.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, ["own"]);
+        assert!(!mcps[0].disabled);
+    }
+
+    #[test]
+    fn managed_settings_are_only_read_when_set() {
+        let dir = TempDir::new();
+        let managed = dir.write("managed-settings.json", "{}");
+        let config = dir.path().join("config");
+
+        let source = LocalSource::with_path(config.clone());
+        assert!(source
+            .settings_files()
+            .iter()
+            .all(|(scope, _)| *scope != Scope::Managed));
+
+        let source = LocalSource::with_path(config).with_managed_settings(Some(managed.clone()));
+        assert!(source.settings_files().contains(&(Scope::Managed, managed)));
+    }
+}
@@ -0,0 +1,512 @@
+use crate::error::{CliError, LocalSourceError};
+use crate::model::{McpChange, Scope};
+use crate::source::json_file::{JsonFile, SavedFile};
+use crate::source::LocalSource;
+use serde_json::{json, Map, Value};
+use std::path::{Path, PathBuf};
+
+/// The scopes `mcp add` and friends can write to, in listing order
+const EDITABLE_SCOPES: [Scope; 3] = [Scope::User, Scope::Local, Scope::Project];
+
+/// A server definition for `mcp add`
+#[derive(Debug, Clone)]
+pub struct McpServerSpec {
+    transport: String,
+    command: Option<String>,
+    args: Vec<String>,
+    url: Option<String>,
+    env: Vec<(String, String)>,
+    headers: Vec<(String, String)>,
+}
+
+impl McpServerSpec {
+    /// A server Claude Code starts as a child process
+    pub fn stdio(command: impl Into<String>, args: Vec<String>) -> Self {
+        Self {
+            transport: "stdio".to_string(),
+            command: Some(command.into()),
+            args,
+            url: None,
+            env: Vec::new(),
+            headers: Vec::new(),
+        }
+    }
+
+    /// A remote server, over streamable HTTP unless changed with `with_transport`
+    pub fn remote(url: impl Into<String>) -> Self {
+        Self {
+            transport: "http".to_string(),
+            command: None,
+            args: Vec::new(),
+            url: Some(url.into()),
+            env: Vec::new(),
+            headers: Vec::new(),
+        }
+    }
+
+    /// `http` or `sse`, for remote servers
+    pub fn with_transport(mut self, transport: impl Into<String>) -> Self {
+        self.transport = transport.into();
+        self
+    }
+
+    pub fn with_env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.push((key.into(), value.into()));
+        self
+    }
+
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// The entry as Claude Code writes it under `mcpServers`
+    fn to_json(&self) -> Result<Value, CliError> {
+        let pairs = |pairs: &[(String, String)]| {
+            Value::Object(
+                pairs
+                    .iter()
+                    .map(|(k, v)| (k.clone(), Value::String(v.clone())))
+                    .collect::<Map<String, Value>>(),
+            )
+        };
+        let invalid = |message: &str| CliError::InvalidArgument(message.to_string());
+
+        match (self.transport.as_str(), &self.command, &self.url) {
+            ("stdio", Some(command), None) => {
+                if !self.headers.is_empty() {
+                    return Err(invalid("headers only apply to http and sse servers"));
+                }
+                Ok(json!({
+                    "type": "stdio",
+                    "command": command,
+                    "args": self.args,
+                    "env": pairs(&self.env),
+                }))
+            }
+            ("http", None, Some(url)) | ("sse", None, Some(url)) => {
+                if !self.env.is_empty() {
+                    return Err(invalid("environment variables only apply to stdio servers"));
+                }
+                let mut entry = json!({ "type": self.transport, "url": url });
+                if !self.headers.is_empty() {
+                    entry["headers"] = pairs(&self.headers);
+                }
+                Ok(entry)
+            }
+            ("stdio", _, _) => Err(invalid("stdio servers need a command and no URL")),
+            ("http", _, _) | ("sse", _, _) => {
+                Err(invalid("remote servers need a URL and no command"))
+            }
+            (other, _, _) => Err(invalid(&format!(
+                "unknown transport '{}' (expected stdio, http or sse)",
+                other
+            ))),
+        }
+    }
+}
+
+/// Where a server is defined: the file and the keys leading to its `mcpServers`
+struct McpLocation {
+    scope: Scope,
+    file: PathBuf,
+    keys: Vec<String>,
+}
+
+impl McpLocation {
+    fn keys(&self) -> Vec<&str> {
+        self.keys.iter().map(String::as_str).collect()
+    }
+}
+
+impl LocalSource {
+    /// Add a server to the file `claude mcp add --scope <scope>` writes
+    pub(crate) fn add_mcp(
+        &self,
+        name: &str,
+        scope: Scope,
+        spec: &McpServerSpec,
+    ) -> Result<(McpChange, SavedFile), CliError> {
+        check_server_name(name)?;
+        let entry = spec.to_json()?;
+        let location = self.mcp_location(scope)?;
+
+        let mut file = JsonFile::open(&location.file)?;
+        let servers = file.object_mut(&location.keys())?;
+        if servers.contains_key(name) {
+            return Err(CliError::AlreadyExists(format!(
+                "{} MCP server '{}' in {}",
+                scope,
+                name,
+                location.file.display()
+            )));
+        }
+        servers.insert(name.to_string(), entry);
+        let saved = file.save()?;
+        Ok((change("added", name, &location, &saved), saved))
+    }
+
+    pub(crate) fn remove_mcp(
+        &self,
+        name: &str,
+        scope: Option<Scope>,
+    ) -> Result<(McpChange, SavedFile), CliError> {
+        let location = self.find_mcp(name, scope)?;
+        let mut file = JsonFile::open(&location.file)?;
+        file.object_mut(&location.keys())?.shift_remove(name);
+        self.replace_disabled(&mut file, &location, name, None)?;
+        let saved = file.save()?;
+        Ok((change("removed", name, &location, &saved), saved))
+    }
+
+    /// Rename a server in place, keeping its position in the file
+    pub(crate) fn rename_mcp(
+        &self,
+        name: &str,
+        new_name: &str,
+        scope: Option<Scope>,
+    ) -> Result<(McpChange, SavedFile), CliError> {
+        check_server_name(new_name)?;
+        let location = self.find_mcp(name, scope)?;
+        let mut file = JsonFile::open(&location.file)?;
+
+        let servers = file.object_mut(&location.keys())?;
+        if servers.contains_key(new_name) {
+            return Err(CliError::AlreadyExists(format!(
+                "{} MCP server '{}' in {}",
+                location.scope,
+                new_name,
+                location.file.display()
+            )));
+        }
+        let renamed: Map<String, Value> = std::mem::take(servers)
+            .into_iter()
+            .map(|(key, value)| {
+                if key == name {
+                    (new_name.to_string(), value)
+                } else {
+                    (key, value)
+                }
+            })
+            .collect();
+        *servers = renamed;
+
+        // Keep the server disabled under its new name
+        self.replace_disabled(&mut file, &location, name, Some(new_name))?;
+
+        let saved = file.save()?;
+        let mut change = change("renamed", new_name, &location, &saved);
+        change.renamed_from = Some(name.to_string());
+        Ok((change, saved))
+    }
+
+    /// Turn a server on or off for the current project. `.mcp.json` servers
+    /// are listed in `disabledMcpjsonServers` of the project's local settings,
+    /// user and local servers in `disabledMcpServers` of the project's entry
+    /// in `~/.claude.json`.
+    pub(crate) fn set_mcp_enabled(
+        &self,
+        name: &str,
+        enabled: bool,
+        scope: Option<Scope>,
+    ) -> Result<(McpChange, SavedFile), CliError> {
+        let server = self.find_mcp(name, scope)?;
+
+        let (path, keys, disabled_key) = if server.scope == Scope::Project {
+            let local_settings = self
+                .project_claude_dir()
+                .map(|dir| dir.join("settings.local.json"))
+                .ok_or_else(|| LocalSourceError::ConfigNotFound(PathBuf::from(".claude")))?;
+            // Enabling edits whichever settings file disables the server,
+            // managed settings excepted
+            let disabling = self
+                .settings_files()
+                .into_iter()
+                .filter(|(scope, _)| *scope != Scope::Managed)
+                .map(|(_, path)| path)
+                .find(|path| enabled && disables(path, name));
+            (
+                disabling.unwrap_or(local_settings),
+                Vec::new(),
+                "disabledMcpjsonServers",
+            )
+        } else {
+            let keys = vec!["projects".to_string(), self.claude_json_project()];
+            (self.claude_json().to_path_buf(), keys, "disabledMcpServers")
+        };
+        let keys: Vec<&str> = keys.iter().map(String::as_str).collect();
+
+        let mut file = JsonFile::open(&path)?;
+        let disabled = file.string_list_mut(&keys, disabled_key)?;
+        disabled.retain(|entry| entry.as_str() != Some(name));
+        if !enabled {
+            disabled.push(Value::String(name.to_string()));
+        }
+        prune(&mut file, &keys, disabled_key)?;
+
+        if server.scope == Scope::Project {
+            let approved = file.string_list_mut(&keys, "enabledMcpjsonServers")?;
+            approved.retain(|entry| entry.as_str() != Some(name));
+            if enabled {
+                approved.push(Value::String(name.to_string()));
+            }
+            prune(&mut file, &keys, "enabledMcpjsonServers")?;
+        }
+
+        let saved = file.save()?;
+        let action = if enabled { "enabled" } else { "disabled" };
+        let location = McpLocation {
+            scope: server.scope,
+            file: path,
+            keys: Vec::new(),
+        };
+        Ok((change(action, name, &location, &saved), saved))
+    }
+
+    /// Rename or drop `name` in the current project's `disabledMcpServers`,
+    /// when that list is in the file being edited
+    fn replace_disabled(
+        &self,
+        file: &mut JsonFile,
+        location: &McpLocation,
+        name: &str,
+        new_name: Option<&str>,
+    ) -> Result<(), CliError> {
+        if location.scope == Scope::Project || location.file != self.claude_json() {
+            return Ok(());
+        }
+        let project = self.claude_json_project();
+        let keys = ["projects", project.as_str()];
+        if file.object(&keys).is_none() {
+            return Ok(());
+        }
+        let disabled = file.string_list_mut(&keys, "disabledMcpServers")?;
+        match new_name {
+            Some(new_name) => {
+                for entry in disabled.iter_mut().filter(|e| e.as_str() == Some(name)) {
+                    *entry = Value::String(new_name.to_string());
+                }
+            }
+            None => disabled.retain(|entry| entry.as_str() != Some(name)),
+        }
+        prune(file, &keys, "disabledMcpServers")
+    }
+
+    /// The one place `name` is defined, within `scope` if given
+    fn find_mcp(&self, name: &str, scope: Option<Scope>) -> Result<McpLocation, CliError> {
+        let mut found = Vec::new();
+        let mut names = Vec::new();
+        for location in self.mcp_locations(scope)? {
+            let file = match JsonFile::open(&location.file) {
+                Ok(file) => file,
+                Err(_) => continue,
+            };
+            if let Some(servers) = file.object(&location.keys()) {
+                names.extend(servers.keys().cloned());
+                if servers.contains_key(name) {
+                    found.push(location);
+                }
+            }
+        }
+
+        match found.len() {
+            0 => Err(CliError::NotFound(name.to_string(), names)),
+            1 => Ok(found.remove(0)),
+            _ => Err(CliError::Ambiguous(
+                name.to_string(),
+                found
+                    .iter()
+                    .map(|l| format!("{} ({})", l.scope, l.file.display()))
+                    .collect(),
+            )),
+        }
+    }
+
+    /// Every file servers of `scope` are read from, or of all editable scopes
+    fn mcp_locations(&self, scope: Option<Scope>) -> Result<Vec<McpLocation>, CliError> {
+        let explicit = scope.is_some();
+        let scopes = match scope {
+            Some(scope) => vec![scope],
+            None => EDITABLE_SCOPES.to_vec(),
+        };
+
+        let mut locations = Vec::new();
+        for scope in scopes {
+            // Servers may also sit in the user's settings.json or mcp.json
+            if scope == Scope::User {
+                for file in ["settings.json", "mcp.json"] {
+                    locations.push(McpLocation {
+                        scope,
+                        file: self.claude_dir().join(file),
+                        keys: vec!["mcpServers".to_string()],
+                    });
+                }
+            }
+            // Searching every scope outside a project skips the project one
+            if scope == Scope::Project && !explicit && self.project_root().is_none() {
+                continue;
+            }
+            locations.push(self.mcp_location(scope)?);
+        }
+        Ok(locations)
+    }
+
+    /// The file and keys `claude mcp add --scope <scope>` writes to
+    fn mcp_location(&self, scope: Scope) -> Result<McpLocation, CliError> {
+        let servers = "mcpServers".to_string();
+        let (file, keys) = match scope {
+            Scope::User => (self.claude_json().to_path_buf(), vec![servers]),
+            Scope::Local => (
+                self.claude_json().to_path_buf(),
+                vec!["projects".to_string(), self.claude_json_project(), servers],
+            ),
+            Scope::Project => {
+                let root = self
+                    .project_root()
+                    .ok_or_else(|| LocalSourceError::ConfigNotFound(PathBuf::from(".mcp.json")))?;
+                (root.join(".mcp.json"), vec![servers])
+            }
+            Scope::Managed => {
+                return Err(CliError::InvalidArgument(
+                    "managed MCP servers cannot be edited".to_string(),
+                ))
+            }
+        };
+        Ok(McpLocation { scope, file, keys })
+    }
+
+    /// The key of the current project under `projects` in `~/.claude.json`:
+    /// the project root, or the working directory outside a project. An
+    /// existing entry for exactly the working directory is kept.
+    fn claude_json_project(&self) -> String {
+        let key = |path: Option<&Path>| path.map(|p| p.display().to_string());
+        let root = key(self.project_root());
+        let cwd = key(self.current_dir());
+
+        let has_entry = |key: &String| {
+            JsonFile::open(self.claude_json())
+                .ok()
+                .and_then(|file| file.object(&["projects", key]).map(|_| ()))
+                .is_some()
+        };
+        match (root, cwd) {
+            (Some(root), Some(cwd)) if !has_entry(&root) && has_entry(&cwd) => cwd,
+            (root, cwd) => root.or(cwd).unwrap_or_default(),
+        }
+    }
+}
+
+fn change(action: &str, name: &str, location: &McpLocation, saved: &SavedFile) -> McpChange {
+    McpChange {
+        action: action.to_string(),
+        name: name.to_string(),
+        renamed_from: None,
+        scope: location.scope,
+        file: saved.path.clone(),
+        backup: saved.backup.clone(),
+    }
+}
+
+/// Claude Code accepts letters, digits, hyphens and underscores
+fn check_server_name(name: &str) -> Result<(), CliError> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(CliError::InvalidName(
+            name.to_string(),
+            vec!["MCP server names may only contain letters, digits, '-' and '_'".to_string()],
+        ))
+    }
+}
+
+/// Drop a list left empty by an edit rather than writing `[]`
+fn prune(file: &mut JsonFile, keys: &[&str], list: &str) -> Result<(), CliError> {
+    let object = file.object_mut(keys)?;
+    if object
+        .get(list)
+        .and_then(Value::as_array)
+        .is_some_and(Vec::is_empty)
+    {
+        object.shift_remove(list);
+    }
+    Ok(())
+}
+
+/// Whether the settings file at `path` disables `.mcp.json` server `name`
+fn disables(path: &Path, name: &str) -> bool {
+    JsonFile::open(path)
+        .ok()
+        .and_then(|file| {
+            file.object(&[])
+                .and_then(|settings| settings.get("disabledMcpjsonServers"))
+                .and_then(Value::as_array)
+                .map(|list| list.iter().any(|entry| entry.as_str() == Some(name)))
+        })
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+    use std::fs;
+
+    /// The `projects` keys holding `name` in `.claude.json` under `dir`
+    fn projects_with(dir: &TempDir, name: &str) -> Vec<String> {
+        let file = JsonFile::open(&dir.path().join("config/.claude.json")).unwrap();
+        let projects = file.object(&["projects"]).unwrap();
+        projects
+            .iter()
+            .filter(|(_, entry)| entry["mcpServers"].get(name).is_some())
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+
+    #[test]
+    fn local_servers_are_added_under_the_exact_project_root() {
+        let dir = TempDir::new();
+        let parent = dir.path().join("work");
+        let project = parent.join("repo");
+        fs::create_dir_all(project.join(".git")).unwrap();
+        let claude_json = json!({ "projects": { parent.display().to_string(): {} } });
+        dir.write("config/.claude.json", &claude_json.to_string());
+
+        let source =
+            LocalSource::with_path(dir.path().join("config")).with_current_dir(project.join("src"));
+        source
+            .add_mcp(
+                "db",
+                Scope::Local,
+                &McpServerSpec::stdio("true", Vec::new()),
+            )
+            .unwrap();
+
+        assert_eq!(projects_with(&dir, "db"), [project.display().to_string()]);
+    }
+
+    #[test]
+    fn an_existing_entry_for_the_working_directory_is_kept() {
+        let dir = TempDir::new();
+        let project = dir.path().join("repo");
+        let cwd = project.join("src");
+        fs::create_dir_all(project.join(".git")).unwrap();
+        let claude_json = json!({ "projects": { cwd.display().to_string(): {} } });
+        dir.write("config/.claude.json", &claude_json.to_string());
+
+        let source =
+            LocalSource::with_path(dir.path().join("config")).with_current_dir(cwd.clone());
+        source
+            .add_mcp(
+                "db",
+                Scope::Local,
+                &McpServerSpec::stdio("true", Vec::new()),
+            )
+            .unwrap();
+
+        assert_eq!(projects_with(&dir, "db"), [cwd.display().to_string()]);
+    }
+}
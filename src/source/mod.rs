@@ -1,7 +1,18 @@
 use crate::error::CliError;
-use crate::model::{McpDetail, McpServer, Skill, SkillDetail};
+use crate::model::{
+    Agent, AgentDetail, Command, Hook, McpDetail, McpServer, Plugin, Skill, SkillDetail, Warning,
+};
 
+pub mod agent;
+pub mod api;
+pub mod command;
+pub(crate) mod frontmatter;
+pub mod hook;
+pub(crate) mod json_file;
 pub mod local;
+pub mod mcp_edit;
+pub mod plugin;
+pub mod registry;
 
 pub trait SkillSource {
     fn list_skills(&self) -> Result<Vec<Skill>, CliError>;
@@ -11,6 +22,136 @@ pub trait SkillSource {
 pub trait McpSource {
     fn list_mcps(&self) -> Result<Vec<McpServer>, CliError>;
     fn get_mcp_detail(&self, name: &str) -> Result<McpDetail, CliError>;
+
+    /// Every definition of every server, including ones another file shadows.
+    /// `all_projects` adds per-project servers of projects other than this one.
+    fn list_mcp_definitions(&self, _all_projects: bool) -> Result<Vec<McpServer>, CliError> {
+        self.list_mcps()
+    }
+}
+
+pub trait CommandSource {
+    fn list_commands(&self) -> Result<Vec<Command>, CliError>;
+}
+
+pub trait AgentSource {
+    fn list_agents(&self) -> Result<Vec<Agent>, CliError>;
+    fn get_agent_detail(&self, name: &str) -> Result<AgentDetail, CliError>;
+}
+
+pub trait HookSource {
+    fn list_hooks(&self) -> Result<Vec<Hook>, CliError>;
+}
+
+pub trait PluginSource {
+    fn list_plugins(&self) -> Result<Vec<Plugin>, CliError>;
+}
+
+/// A data source that can be registered with `ListService`.
+/// Each accessor returns the listing trait the source implements, if any.
+pub trait Source {
+    fn skills(&self) -> Option<&dyn SkillSource> {
+        None
+    }
+
+    fn mcps(&self) -> Option<&dyn McpSource> {
+        None
+    }
+
+    fn commands(&self) -> Option<&dyn CommandSource> {
+        None
+    }
+
+    fn agents(&self) -> Option<&dyn AgentSource> {
+        None
+    }
+
+    fn hooks(&self) -> Option<&dyn HookSource> {
+        None
+    }
+
+    fn plugins(&self) -> Option<&dyn PluginSource> {
+        None
+    }
+
+    /// Problems skipped over since the last call
+    fn take_warnings(&self) -> Vec<Warning> {
+        Vec::new()
+    }
+}
+
+impl Source for LocalSource {
+    fn skills(&self) -> Option<&dyn SkillSource> {
+        Some(self)
+    }
+
+    fn mcps(&self) -> Option<&dyn McpSource> {
+        Some(self)
+    }
+
+    fn commands(&self) -> Option<&dyn CommandSource> {
+        Some(self)
+    }
+
+    fn agents(&self) -> Option<&dyn AgentSource> {
+        Some(self)
+    }
+
+    fn hooks(&self) -> Option<&dyn HookSource> {
+        Some(self)
+    }
+
+    fn plugins(&self) -> Option<&dyn PluginSource> {
+        Some(self)
+    }
+
+    fn take_warnings(&self) -> Vec<Warning> {
+        LocalSource::take_warnings(self)
+    }
+}
+
+impl Source for ApiSource {
+    fn skills(&self) -> Option<&dyn SkillSource> {
+        Some(self)
+    }
+}
+
+/// Find an item by case-insensitive partial name match.
+/// When several items match, an exact match wins; the last one is taken so
+/// that lists sorted by scope prefer the most specific scope.
+pub(crate) fn find_by_name<'a, T>(
+    items: &'a [T],
+    name: &str,
+    item_name: impl Fn(&T) -> &str,
+) -> Result<&'a T, CliError> {
+    let name_lower = name.to_lowercase();
+
+    let matches: Vec<&T> = items
+        .iter()
+        .filter(|item| item_name(item).to_lowercase().contains(&name_lower))
+        .collect();
+
+    if matches.is_empty() {
+        let all_names: Vec<String> = items.iter().map(|i| item_name(i).to_string()).collect();
+        return Err(CliError::NotFound(name.to_string(), all_names));
+    }
+
+    if matches.len() > 1 {
+        if let Some(exact) = matches
+            .iter()
+            .rev()
+            .find(|item| item_name(item).to_lowercase() == name_lower)
+        {
+            return Ok(exact);
+        }
+        let suggestions: Vec<String> = matches.iter().map(|i| item_name(i).to_string()).collect();
+        return Err(CliError::NotFound(name.to_string(), suggestions));
+    }
+
+    Ok(matches[0])
 }
 
-pub use local::LocalSource;
+pub use api::ApiSource;
+pub use local::{LocalLayer, LocalSource};
+pub use mcp_edit::McpServerSpec;
+pub use registry::SourceRegistry;
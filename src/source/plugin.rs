@@ -0,0 +1,357 @@
+use crate::error::{CliError, LocalSourceError};
+use crate::model::{Command, McpConfig, McpServer, Plugin, Scope, Skill};
+use crate::source::hook::read_hooks;
+use crate::source::local::{read_mcp_servers, LocalLayer, McpServerConfig};
+use crate::source::{LocalSource, PluginSource};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Placeholder plugins use in their configs to refer to their install directory
+const PLUGIN_ROOT_VAR: &str = "${CLAUDE_PLUGIN_ROOT}";
+
+/// `plugins/installed_plugins.json`
+#[derive(Debug, Deserialize)]
+struct InstalledPlugins {
+    #[serde(default)]
+    plugins: HashMap<String, InstallEntries>,
+}
+
+/// Version 1 stores one entry per plugin, version 2 a list of per-scope installs
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum InstallEntries {
+    One(InstallEntry),
+    Many(Vec<InstallEntry>),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct InstallEntry {
+    #[serde(rename = "installPath")]
+    install_path: PathBuf,
+    version: Option<String>,
+    scope: Option<String>,
+    #[serde(rename = "projectPath")]
+    project_path: Option<PathBuf>,
+}
+
+/// `plugins/known_marketplaces.json`, keyed by marketplace name
+#[derive(Debug, Deserialize)]
+struct KnownMarketplace {
+    source: Option<Value>,
+}
+
+/// `.claude-plugin/plugin.json`
+#[derive(Debug, Default, Deserialize)]
+struct PluginManifest {
+    name: Option<String>,
+    version: Option<String>,
+    description: Option<String>,
+    commands: Option<Value>,
+    agents: Option<Value>,
+    hooks: Option<Value>,
+    #[serde(rename = "mcpServers")]
+    mcp_servers: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EnabledPluginsSettings {
+    #[serde(rename = "enabledPlugins", default)]
+    enabled_plugins: HashMap<String, Value>,
+}
+
+/// A plugin together with the skills and servers it contributes
+#[derive(Debug, Clone)]
+pub(crate) struct LoadedPlugin {
+    plugin: Plugin,
+    skills: Vec<Skill>,
+    mcps: Vec<McpServer>,
+}
+
+impl PluginSource for LocalSource {
+    fn list_plugins(&self) -> Result<Vec<Plugin>, CliError> {
+        if !self.reads(LocalLayer::Plugin) {
+            return Ok(Vec::new());
+        }
+        let mut plugins: Vec<Plugin> = self
+            .load_plugins()?
+            .iter()
+            .map(|loaded| loaded.plugin.clone())
+            .collect();
+        self.label(&mut plugins, |p| &mut p.config_dir);
+        Ok(plugins)
+    }
+}
+
+impl LocalSource {
+    /// Skills shipped by enabled plugins
+    pub(crate) fn plugin_skills(&self) -> Result<Vec<Skill>, CliError> {
+        Ok(self
+            .load_plugins()?
+            .iter()
+            .filter(|loaded| loaded.plugin.enabled)
+            .flat_map(|loaded| loaded.skills.iter().cloned())
+            .collect())
+    }
+
+    /// MCP servers shipped by enabled plugins
+    pub(crate) fn plugin_mcps(&self) -> Result<Vec<McpServer>, CliError> {
+        Ok(self
+            .load_plugins()?
+            .iter()
+            .filter(|loaded| loaded.plugin.enabled)
+            .flat_map(|loaded| loaded.mcps.iter().cloned())
+            .collect())
+    }
+
+    /// Installed plugins, read on first use; listing skills, servers and
+    /// plugins all go through the same manifests
+    fn load_plugins(&self) -> Result<&[LoadedPlugin], CliError> {
+        if let Some(plugins) = self.loaded_plugins().get() {
+            return Ok(plugins);
+        }
+        let plugins = self.read_plugins()?;
+        Ok(self.loaded_plugins().get_or_init(|| plugins))
+    }
+
+    fn read_plugins(&self) -> Result<Vec<LoadedPlugin>, CliError> {
+        let plugins_dir = self.claude_dir().join("plugins");
+        let installed_path = plugins_dir.join("installed_plugins.json");
+        let installed: InstalledPlugins =
+            match self.tolerate(&installed_path, read_json(&installed_path)) {
+                Some(installed) => installed,
+                None => return Ok(Vec::new()),
+            };
+        let marketplaces_path = plugins_dir.join("known_marketplaces.json");
+        let marketplaces: HashMap<String, KnownMarketplace> = self
+            .tolerate(&marketplaces_path, read_json(&marketplaces_path))
+            .unwrap_or_default();
+        let enabled = self.enabled_plugins();
+
+        let mut plugins = Vec::new();
+        for (id, entries) in installed.plugins {
+            let entry = match self.applicable_install(entries) {
+                Some(entry) => entry,
+                None => continue,
+            };
+
+            let (name, marketplace) = match id.split_once('@') {
+                Some((name, marketplace)) => (name.to_string(), Some(marketplace.to_string())),
+                None => (id.clone(), None),
+            };
+            let marketplace_source = marketplace
+                .as_ref()
+                .and_then(|m| marketplaces.get(m))
+                .and_then(|m| m.source.as_ref())
+                .map(describe_marketplace_source);
+
+            let enabled = enabled.get(&id).copied().unwrap_or(false);
+            let path = entry.install_path.clone();
+            let loaded =
+                self.load_plugin(id, name, marketplace, marketplace_source, enabled, entry);
+            // A broken plugin is skipped rather than hiding the others
+            if let Some(loaded) = self.tolerate(&path, loaded.map(Some)) {
+                plugins.push(loaded);
+            }
+        }
+
+        plugins.sort_by(|a, b| a.plugin.id.cmp(&b.plugin.id));
+        Ok(plugins)
+    }
+
+    /// Pick the install that applies here: a user-scoped install, or a
+    /// project/local install for the current project
+    fn applicable_install(&self, entries: InstallEntries) -> Option<InstallEntry> {
+        let entries = match entries {
+            InstallEntries::One(entry) => vec![entry],
+            InstallEntries::Many(entries) => entries,
+        };
+
+        entries
+            .into_iter()
+            .find(|entry| match entry.scope.as_deref() {
+                None | Some("user") => true,
+                Some(_) => entry
+                    .project_path
+                    .as_ref()
+                    .is_some_and(|p| self.is_current_project(p)),
+            })
+    }
+
+    /// `enabledPlugins` merged across settings files; later files win
+    fn enabled_plugins(&self) -> HashMap<String, bool> {
+        let mut enabled = HashMap::new();
+        for (_, path) in self.settings_files() {
+            let settings: Option<EnabledPluginsSettings> = self.tolerate(&path, read_json(&path));
+            for (id, value) in settings.map(|s| s.enabled_plugins).unwrap_or_default() {
+                enabled.insert(id, value.as_bool().unwrap_or(false));
+            }
+        }
+        enabled
+    }
+
+    fn load_plugin(
+        &self,
+        id: String,
+        name: String,
+        marketplace: Option<String>,
+        marketplace_source: Option<String>,
+        enabled: bool,
+        entry: InstallEntry,
+    ) -> Result<LoadedPlugin, CliError> {
+        let root = entry.install_path;
+        let manifest: PluginManifest =
+            read_json(&root.join(".claude-plugin").join("plugin.json"))?.unwrap_or_default();
+
+        let mut skills = self.scan_skills_dir(&root.join("skills"), Scope::User)?;
+        for skill in &mut skills {
+            skill.plugin = Some(id.clone());
+        }
+
+        let mut commands: Vec<Command> = Vec::new();
+        for dir in with_default(&root, "commands", manifest.commands.as_ref()) {
+            self.scan_commands_dir(&dir, &[], Scope::User, &mut commands)?;
+        }
+
+        let mut agents = Vec::new();
+        for dir in with_default(&root, "agents", manifest.agents.as_ref()) {
+            agents.extend(self.scan_agents_dir(&dir, Scope::User)?);
+        }
+
+        let mut hooks = Vec::new();
+        for file in with_default(&root, "hooks/hooks.json", manifest.hooks.as_ref()) {
+            if file.exists() {
+                hooks.extend(read_hooks(&file, Scope::User)?);
+            }
+        }
+
+        // Servers by name, with the file each was read from
+        let mcp_json = root.join(".mcp.json");
+        let mut servers: HashMap<String, (McpServerConfig, PathBuf)> = read_mcp_servers(&mcp_json)?
+            .into_iter()
+            .map(|(name, config)| (name, (config, mcp_json.clone())))
+            .collect();
+        match manifest.mcp_servers {
+            Some(Value::String(ref path)) => {
+                let file = root.join(path);
+                for (name, config) in read_mcp_servers(&file)? {
+                    servers.insert(name, (config, file.clone()));
+                }
+            }
+            Some(Value::Object(mut inline)) => {
+                // Inline servers may be wrapped in an `mcpServers` key
+                let map = match inline.remove("mcpServers") {
+                    Some(wrapped) => wrapped,
+                    None => Value::Object(inline),
+                };
+                let inline: HashMap<String, McpServerConfig> = serde_json::from_value(map)
+                    .map_err(|e| LocalSourceError::InvalidConfig(format!("plugin.json: {}", e)))?;
+                let manifest_path = root.join(".claude-plugin").join("plugin.json");
+                for (name, config) in inline {
+                    servers.insert(name, (config, manifest_path.clone()));
+                }
+            }
+            _ => {}
+        }
+
+        let mut mcps: Vec<McpServer> = servers
+            .into_iter()
+            .map(|(name, (config, origin))| {
+                McpServer::new(name)
+                    .with_config(expand_plugin_root(config.into(), &root))
+                    .with_plugin(id.clone())
+                    .with_origin(origin)
+            })
+            .collect();
+        mcps.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let mut plugin = Plugin {
+            id,
+            name: manifest.name.unwrap_or(name),
+            marketplace,
+            marketplace_source,
+            version: manifest.version.or(entry.version),
+            description: manifest.description,
+            enabled,
+            path: root,
+            skills: skills.iter().map(|s| s.name.clone()).collect(),
+            commands: commands.into_iter().map(|c| c.name).collect(),
+            agents: agents.into_iter().map(|a| a.name).collect(),
+            hooks: hooks.into_iter().map(|h| h.event).collect(),
+            mcps: mcps.iter().map(|m| m.name.clone()).collect(),
+            config_dir: None,
+        };
+        plugin.commands.sort();
+        plugin.agents.sort();
+        plugin.hooks.sort();
+        plugin.hooks.dedup();
+
+        Ok(LoadedPlugin {
+            plugin,
+            skills,
+            mcps,
+        })
+    }
+}
+
+/// The default location plus any extra paths listed in the manifest
+fn with_default(root: &Path, default: &str, extra: Option<&Value>) -> Vec<PathBuf> {
+    let mut paths = vec![root.join(default)];
+    match extra {
+        Some(Value::String(path)) => paths.push(root.join(path)),
+        Some(Value::Array(items)) => paths.extend(
+            items
+                .iter()
+                .filter_map(Value::as_str)
+                .map(|path| root.join(path)),
+        ),
+        _ => {}
+    }
+    paths.dedup();
+    paths
+}
+
+/// Substitute `${CLAUDE_PLUGIN_ROOT}` in the command, arguments and environment
+fn expand_plugin_root(mut config: McpConfig, root: &Path) -> McpConfig {
+    let root = root.display().to_string();
+    let expand = |value: &mut String| *value = value.replace(PLUGIN_ROOT_VAR, &root);
+
+    config.command.iter_mut().for_each(expand);
+    config.args.iter_mut().flatten().for_each(expand);
+    config
+        .env
+        .iter_mut()
+        .flat_map(|e| e.values_mut())
+        .for_each(expand);
+    config
+}
+
+fn describe_marketplace_source(source: &Value) -> String {
+    let field = |key: &str| source.get(key).and_then(Value::as_str);
+    match field("source") {
+        Some("github") => format!("github:{}", field("repo").unwrap_or("?")),
+        Some("git") => field("url").unwrap_or("git").to_string(),
+        Some("directory") | Some("local") => field("path").unwrap_or("local").to_string(),
+        Some(other) => other.to_string(),
+        None => source.to_string(),
+    }
+}
+
+/// Read and parse a JSON file, returning `None` when it does not exist
+pub(crate) fn read_json<T: DeserializeOwned>(path: &Path) -> Result<Option<T>, CliError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(path)?;
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let value = serde_json::from_str(&content)
+        .map_err(|e| LocalSourceError::InvalidConfig(format!("{}: {}", file_name, e)))?;
+    Ok(Some(value))
+}
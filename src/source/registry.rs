@@ -0,0 +1,94 @@
+use crate::error::CliError;
+use crate::source::Source;
+
+/// A source registered under a name, e.g. `user` or `api`
+pub struct RegisteredSource {
+    pub name: String,
+    /// Higher priorities win: their items sort after (and so override) the
+    /// same name from lower ones, and `show` reads that name from them
+    pub priority: i32,
+    pub source: Box<dyn Source>,
+}
+
+/// The named sources `ListService` reads from
+#[derive(Default)]
+pub struct SourceRegistry {
+    sources: Vec<RegisteredSource>,
+}
+
+impl SourceRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a source, replacing any existing source with the same name
+    pub fn register(
+        &mut self,
+        name: impl Into<String>,
+        priority: i32,
+        source: impl Source + 'static,
+    ) {
+        self.insert(RegisteredSource {
+            name: name.into(),
+            priority,
+            source: Box::new(source),
+        });
+    }
+
+    pub fn with_source(
+        mut self,
+        name: impl Into<String>,
+        priority: i32,
+        source: impl Source + 'static,
+    ) -> Self {
+        self.register(name, priority, source);
+        self
+    }
+
+    /// Move every source of `other` into this registry
+    pub fn extend(&mut self, other: SourceRegistry) {
+        for source in other.sources {
+            self.insert(source);
+        }
+    }
+
+    fn insert(&mut self, source: RegisteredSource) {
+        self.sources.retain(|s| s.name != source.name);
+        self.sources.push(source);
+        // Stable, so equal priorities keep their registration order
+        self.sources.sort_by_key(|s| s.priority);
+    }
+
+    /// Keep only the named sources. A name also selects the sources named
+    /// `<name>:<qualifier>`, e.g. `user` selects `user:/work/.claude`.
+    /// Fails on a name that matches nothing.
+    pub fn select(&mut self, names: &[String]) -> Result<(), CliError> {
+        if let Some(unknown) = names.iter().find(|n| !self.contains(n)) {
+            return Err(CliError::UnknownSource(unknown.clone(), self.names()));
+        }
+        self.sources
+            .retain(|s| names.iter().any(|n| name_matches(&s.name, n)));
+        Ok(())
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.sources.iter().any(|s| name_matches(&s.name, name))
+    }
+
+    /// Registered names in increasing order of priority
+    pub fn names(&self) -> Vec<String> {
+        self.sources.iter().map(|s| s.name.clone()).collect()
+    }
+
+    /// Sources in increasing order of priority
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &RegisteredSource> {
+        self.sources.iter()
+    }
+}
+
+fn name_matches(source: &str, name: &str) -> bool {
+    source == name
+        || source
+            .strip_prefix(name)
+            .is_some_and(|rest| rest.starts_with(':'))
+}
@@ -0,0 +1,55 @@
+#!/bin/sh
+# A minimal stdio MCP server for the client tests. The first argument picks
+# how it behaves: ok, silent (never answers), exit (quits at once), error
+# (fails every request after initialize), cycle (tools/list pages loop
+# A -> B -> A) or endless (every tools/list page has a new cursor).
+mode=${1:-ok}
+[ "$mode" = exit ] && exit 0
+
+respond() {
+    echo "{\"jsonrpc\":\"2.0\",\"id\":$id,$1}"
+}
+
+while IFS= read -r line; do
+    id=$(printf '%s\n' "$line" | sed -n 's/.*"id":\([0-9][0-9]*\).*/\1/p')
+    [ -z "$id" ] && continue
+    [ "$mode" = silent ] && continue
+
+    case "$line" in
+    *'"method":"initialize"'*)
+        # Noise a client has to skip: a log line and a notification
+        echo "fake server starting"
+        echo '{"jsonrpc":"2.0","method":"notifications/message","params":{}}'
+        respond '"result":{"protocolVersion":"2025-06-18","serverInfo":{"name":"fake","version":"1.2.3"},"capabilities":{"tools":{},"prompts":{}}}'
+        ;;
+    *)
+        if [ "$mode" = error ]; then
+            respond '"error":{"code":-32601,"message":"no such method"}'
+            continue
+        fi
+        case "$mode:$line" in
+        cycle:*'"method":"tools/list"'*'"cursor":"A"'*)
+            respond '"result":{"tools":[{"name":"b"}],"nextCursor":"B"}'
+            ;;
+        cycle:*'"method":"tools/list"'*)
+            respond '"result":{"tools":[{"name":"a"}],"nextCursor":"A"}'
+            ;;
+        endless:*'"method":"tools/list"'*)
+            respond "\"result\":{\"tools\":[],\"nextCursor\":\"c$id\"}"
+            ;;
+        *'"method":"tools/list"'*'"cursor":"page2"'*)
+            respond '"result":{"tools":[{"name":"write"}]}'
+            ;;
+        *'"method":"tools/list"'*)
+            respond '"result":{"tools":[{"name":"read","description":"Read a file"}],"nextCursor":"page2"}'
+            ;;
+        *'"method":"prompts/list"'*)
+            respond '"result":{"prompts":[]}'
+            ;;
+        *)
+            respond '"error":{"code":-32601,"message":"no such method"}'
+            ;;
+        esac
+        ;;
+    esac
+done
@@ -0,0 +1,81 @@
+#![cfg(unix)]
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// A project whose `.mcp.json` server leaves a marker file when started,
+/// under a fresh home directory
+struct Project {
+    root: PathBuf,
+}
+
+impl Project {
+    fn new(name: &str, approved: bool) -> Self {
+        let root =
+            std::env::temp_dir().join(format!("claude-list-show-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("home/.claude")).unwrap();
+        fs::create_dir_all(root.join("repo/.git")).unwrap();
+
+        let marker = root.join("started");
+        let server = serde_json::json!({ "mcpServers": { "db": {
+            "command": "sh",
+            "args": ["-c", format!("touch '{}'", marker.display())]
+        } } });
+        fs::write(root.join("repo/.mcp.json"), server.to_string()).unwrap();
+        if approved {
+            fs::create_dir_all(root.join("repo/.claude")).unwrap();
+            fs::write(
+                root.join("repo/.claude/settings.local.json"),
+                r#"{"enabledMcpjsonServers": ["db"]}"#,
+            )
+            .unwrap();
+        }
+        Self { root }
+    }
+
+    /// Run `claude-list show db` with `flags` and say whether the server started
+    fn show(&self, flags: &[&str]) -> bool {
+        let output = Command::new(env!("CARGO_BIN_EXE_claude-list"))
+            .args(["show", "db", "--timeout", "2"])
+            .args(flags)
+            .current_dir(self.root.join("repo"))
+            .env("HOME", self.root.join("home"))
+            .env_remove("CLAUDE_CONFIG_DIR")
+            .env_remove("CLAUDE_API_KEY")
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{:?}", output);
+        self.started()
+    }
+
+    fn started(&self) -> bool {
+        self.root.join("started").exists()
+    }
+}
+
+impl Drop for Project {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
+
+#[test]
+fn show_does_not_start_the_server() {
+    let project = Project::new("plain", true);
+    assert!(!project.show(&[]));
+    assert!(!project.show(&["--doctor"]));
+}
+
+#[test]
+fn show_with_check_starts_an_approved_server() {
+    let project = Project::new("check", true);
+    assert!(project.show(&["--check"]));
+}
+
+#[test]
+fn show_with_check_leaves_unapproved_servers_alone() {
+    let project = Project::new("unapproved", false);
+    assert!(!project.show(&["--check"]));
+}
@@ -0,0 +1,169 @@
+#![cfg(unix)]
+
+use claude_list::client::StdioClient;
+use claude_list::error::McpClientError;
+use claude_list::model::{ConnectionStatus, McpConfig, McpDetail, McpTransport, Scope};
+use claude_list::service::health;
+use claude_list::McpServer;
+use std::path::PathBuf;
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The fake server in `tests/fixtures`, started in `mode`
+fn fake_server(mode: &str) -> McpConfig {
+    let script =
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/fake_mcp_server.sh");
+    McpConfig {
+        transport: McpTransport::Stdio,
+        command: Some("sh".to_string()),
+        args: Some(vec![script.display().to_string(), mode.to_string()]),
+        env: None,
+    }
+}
+
+fn detail(config: McpConfig) -> McpDetail {
+    McpDetail {
+        name: "fake".to_string(),
+        config,
+        source_path: PathBuf::from(".mcp.json"),
+        source_type: ".mcp.json".to_string(),
+        scope: Scope::Project,
+        project: None,
+        status: ConnectionStatus::Unknown,
+        health: None,
+        disabled: false,
+        unapproved: false,
+        capabilities: None,
+        resolution: None,
+    }
+}
+
+#[test]
+fn initialize_skips_log_lines_and_notifications() {
+    let mut client = StdioClient::spawn(&fake_server("ok"), TIMEOUT).unwrap();
+    let init = client.initialize().unwrap();
+
+    let info = init.server_info.unwrap();
+    assert_eq!(info.name.as_deref(), Some("fake"));
+    assert_eq!(info.version.as_deref(), Some("1.2.3"));
+    assert_eq!(init.protocol_version.as_deref(), Some("2025-06-18"));
+}
+
+#[test]
+fn list_all_follows_next_cursor() {
+    let mut client = StdioClient::spawn(&fake_server("ok"), TIMEOUT).unwrap();
+    client.initialize().unwrap();
+
+    let tools = client.list_all("tools/list", "tools").unwrap();
+    let names: Vec<&str> = tools.iter().filter_map(|t| t["name"].as_str()).collect();
+    assert_eq!(names, ["read", "write"]);
+}
+
+#[test]
+fn server_errors_carry_code_and_message() {
+    let mut client = StdioClient::spawn(&fake_server("error"), TIMEOUT).unwrap();
+    client.initialize().unwrap();
+
+    match client.request("tools/list", serde_json::json!({})) {
+        Err(McpClientError::Server { code, message }) => {
+            assert_eq!(code, -32601);
+            assert_eq!(message, "no such method");
+        }
+        other => panic!("expected a server error, got {:?}", other),
+    }
+}
+
+#[test]
+fn unanswered_requests_time_out() {
+    let timeout = Duration::from_millis(200);
+    let mut client = StdioClient::spawn(&fake_server("silent"), timeout).unwrap();
+
+    assert!(matches!(
+        client.initialize(),
+        Err(McpClientError::Timeout(t)) if t == timeout
+    ));
+}
+
+#[test]
+fn check_server_reports_health() {
+    let mut mcp = McpServer::new("fake").with_config(fake_server("ok"));
+    health::check_server(&mut mcp, TIMEOUT);
+
+    assert_eq!(mcp.status, ConnectionStatus::Connected);
+    let health = mcp.health.unwrap();
+    assert_eq!(health.server_name.as_deref(), Some("fake"));
+    assert_eq!(health.server_version.as_deref(), Some("1.2.3"));
+}
+
+#[test]
+fn check_server_marks_exited_servers_disconnected() {
+    let mut mcp = McpServer::new("fake").with_config(fake_server("exit"));
+    health::check_server(&mut mcp, TIMEOUT);
+
+    assert_eq!(mcp.status, ConnectionStatus::Disconnected);
+    assert!(mcp.health.is_none());
+}
+
+#[test]
+fn check_server_reports_missing_commands() {
+    let mut config = fake_server("ok");
+    config.command = Some("claude-list-no-such-command".to_string());
+    let mut mcp = McpServer::new("fake").with_config(config);
+    health::check_server(&mut mcp, TIMEOUT);
+
+    assert!(matches!(mcp.status, ConnectionStatus::Error(_)));
+}
+
+#[test]
+fn inspect_server_lists_declared_capabilities() {
+    let mut detail = detail(fake_server("ok"));
+    health::inspect_server(&mut detail, TIMEOUT);
+
+    assert_eq!(detail.status, ConnectionStatus::Connected);
+    let capabilities = detail.capabilities.unwrap();
+    let tools: Vec<&str> = capabilities.tools.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(tools, ["read", "write"]);
+    assert_eq!(
+        capabilities.tools[0].description.as_deref(),
+        Some("Read a file")
+    );
+    // Resources were not declared, so they are not asked for
+    assert!(capabilities.resources.is_empty());
+    assert!(capabilities.prompts.is_empty());
+}
+
+#[test]
+fn list_all_stops_when_cursors_loop() {
+    let mut client = StdioClient::spawn(&fake_server("cycle"), TIMEOUT).unwrap();
+    client.initialize().unwrap();
+
+    let tools = client.list_all("tools/list", "tools").unwrap();
+    let names: Vec<&str> = tools.iter().filter_map(|t| t["name"].as_str()).collect();
+    assert_eq!(names, ["a", "b", "a"]);
+}
+
+#[test]
+fn list_all_gives_up_on_endless_pages() {
+    let mut client = StdioClient::spawn(&fake_server("endless"), TIMEOUT).unwrap();
+    client.initialize().unwrap();
+
+    assert!(matches!(
+        client.list_all("tools/list", "tools"),
+        Err(McpClientError::Protocol(_))
+    ));
+}
+
+#[test]
+fn disabled_and_unapproved_servers_are_not_started() {
+    let mut disabled = McpServer::new("fake").with_config(fake_server("ok"));
+    disabled.disabled = true;
+    health::check_server(&mut disabled, TIMEOUT);
+    assert_eq!(disabled.status, ConnectionStatus::Unknown);
+
+    let mut unapproved = detail(fake_server("ok"));
+    unapproved.unapproved = true;
+    health::inspect_server(&mut unapproved, TIMEOUT);
+    assert_eq!(unapproved.status, ConnectionStatus::Unknown);
+    assert!(unapproved.capabilities.is_none());
+}